@@ -0,0 +1,123 @@
+use crate::{interval::Interval, ray::Ray, vec3::Vec3};
+
+/// Axis-aligned bounding box, stored as one interval per axis.
+#[derive(Debug, Clone, Copy)]
+pub struct Aabb {
+    pub x: Interval,
+    pub y: Interval,
+    pub z: Interval,
+}
+
+impl Aabb {
+    pub fn new(x: Interval, y: Interval, z: Interval) -> Self {
+        let mut result = Aabb { x, y, z };
+        result.pad_to_minimums();
+        result
+    }
+
+    pub fn new_empty() -> Self {
+        Aabb {
+            x: Interval::new_empty(),
+            y: Interval::new_empty(),
+            z: Interval::new_empty(),
+        }
+    }
+
+    pub fn new_from_points(a: Vec3, b: Vec3) -> Self {
+        Aabb::new(
+            Interval::new(a.x.min(b.x), a.x.max(b.x)),
+            Interval::new(a.y.min(b.y), a.y.max(b.y)),
+            Interval::new(a.z.min(b.z), a.z.max(b.z)),
+        )
+    }
+
+    pub fn new_enclosing(a: &Aabb, b: &Aabb) -> Self {
+        Aabb {
+            x: Interval::new_enclosing(&a.x, &b.x),
+            y: Interval::new_enclosing(&a.y, &b.y),
+            z: Interval::new_enclosing(&a.z, &b.z),
+        }
+    }
+
+    pub fn axis_interval(&self, axis: usize) -> &Interval {
+        match axis {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Axis out of bounds"),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.x.min > self.x.max || self.y.min > self.y.max || self.z.min > self.z.max
+    }
+
+    pub fn longest_axis(&self) -> usize {
+        let (x, y, z) = (self.x.size(), self.y.size(), self.z.size());
+        if x > y {
+            if x > z { 0 } else { 2 }
+        } else if y > z {
+            1
+        } else {
+            2
+        }
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let (x, y, z) = (self.x.size(), self.y.size(), self.z.size());
+        2.0 * (x * y + y * z + z * x)
+    }
+
+    /// Midpoint of the box along `axis`, used to order primitives when
+    /// building a BVH. Unbounded axes are treated as centered on the origin.
+    pub fn centroid(&self, axis: usize) -> f64 {
+        let interval = self.axis_interval(axis);
+        let mid = (interval.min + interval.max) / 2.0;
+        if mid.is_finite() { mid } else { 0.0 }
+    }
+
+    /// Slab test: returns whether the ray overlaps the box anywhere inside
+    /// `interval`.
+    pub fn hit(&self, ray: &Ray, interval: &Interval) -> bool {
+        let mut t_min = interval.min;
+        let mut t_max = interval.max;
+        for axis in 0..3 {
+            let bounds = self.axis_interval(axis);
+            let inv_d = 1.0 / ray.direction[axis as isize];
+            let origin = ray.origin[axis as isize];
+
+            let t0 = (bounds.min - origin) * inv_d;
+            let t1 = (bounds.max - origin) * inv_d;
+            let (t0, t1) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+
+            if t0 > t_min {
+                t_min = t0;
+            }
+            if t1 < t_max {
+                t_max = t1;
+            }
+            if t_max <= t_min {
+                return false;
+            }
+        }
+        true
+    }
+
+    // Flat primitives (axis aligned triangles, rectangles) would otherwise
+    // produce a zero-width box that the slab test can miss.
+    fn pad_to_minimums(&mut self) {
+        let delta = 0.0001;
+        if self.x.size() < delta {
+            self.x = self.x.expand(delta);
+        }
+        if self.y.size() < delta {
+            self.y = self.y.expand(delta);
+        }
+        if self.z.size() < delta {
+            self.z = self.z.expand(delta);
+        }
+    }
+}
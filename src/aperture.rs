@@ -0,0 +1,93 @@
+use std::{
+    f64::consts::{FRAC_1_SQRT_2, PI},
+    path::Path,
+    sync::Arc,
+};
+
+use image::ImageError;
+
+use crate::{rng::random, vec3::Vec3};
+
+/// Shape of the lens opening. Out of focus highlights take on this shape.
+#[derive(Debug, Clone)]
+pub enum Aperture {
+    Circle,
+    /// Regular polygon with `blades` corners on the unit circle, turned
+    /// counterclockwise by `rotation` degrees.
+    Polygon { blades: u32, rotation: f64 },
+    Mask(Arc<ApertureMask>),
+}
+
+impl Aperture {
+    // point on the lens, within the unit disk
+    pub fn sample(&self) -> Vec3 {
+        match self {
+            Aperture::Circle => Vec3::random_in_unit_disk(),
+            Aperture::Polygon { blades, rotation } => {
+                let blades = (*blades).max(3);
+                // the polygon is a fan of equal triangles around the center
+                let wedge = 2.0 * PI / blades as f64;
+                let index = ((random::<f64>() * blades as f64) as u32).min(blades - 1);
+                let angle = rotation.to_radians() + wedge * index as f64;
+                let a = Vec3::new(angle.cos(), angle.sin(), 0.0);
+                let b = Vec3::new((angle + wedge).cos(), (angle + wedge).sin(), 0.0);
+                // uniform in the triangle (0, a, b)
+                let (r1, r2) = (random::<f64>().sqrt(), random::<f64>());
+                a * (r1 * (1.0 - r2)) + b * (r1 * r2)
+            }
+            Aperture::Mask(mask) => mask.sample(),
+        }
+    }
+}
+
+/// Aperture shape read from a grayscale image, a square inscribed in the
+/// unit disk of the lens so its corners touch the rim. Brighter texels let
+/// more light through, black ones block it.
+#[derive(Debug)]
+pub struct ApertureMask {
+    pub width: usize,
+    pub height: usize,
+    // running sum of the texel weights, row by row
+    cdf: Vec<f64>,
+}
+
+impl ApertureMask {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Arc<Self>, ImageError> {
+        let image = image::open(path)?.into_luma8();
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let mut total = 0.0;
+        let cdf = image
+            .pixels()
+            .map(|p| {
+                total += p[0] as f64 / 255.0;
+                total
+            })
+            .collect();
+        Ok(Arc::new(ApertureMask { width, height, cdf }))
+    }
+
+    fn sample(&self) -> Vec3 {
+        let total = self.cdf.last().copied().unwrap_or(0.0);
+        if total <= 0.0 {
+            // nothing gets through, fall back to a pinhole
+            return Vec3::new_zero();
+        }
+        let target = random::<f64>() * total;
+        let index = self.cdf.partition_point(|&sum| sum <= target).min(self.cdf.len() - 1);
+        let (col, row) = (index % self.width, index / self.width);
+        let x = (col as f64 + random::<f64>()) / self.width as f64;
+        let y = (row as f64 + random::<f64>()) / self.height as f64;
+        // image rows go down, lens y goes up. The square [-1, 1]^2 is scaled
+        // into the unit disk
+        Vec3::new(2.0 * x - 1.0, 1.0 - 2.0 * y, 0.0) * FRAC_1_SQRT_2
+    }
+}
+
+// Optical vignetting: towards the edges of the frame the lens barrel cuts
+// into the aperture, seen as a circle moved towards the edge by `strength`
+// times the distance from the image center. `film` is that offset, the
+// corners lie at length 1. Lens points outside the barrel are blocked, which
+// gives cat's eye shaped bokeh and darker corners.
+pub fn vignetted(lens: &Vec3, film: &Vec3, strength: f64) -> bool {
+    strength > 0.0 && (*lens - *film * strength).length_squared() > 1.0
+}
@@ -1,13 +1,7 @@
 use std::{f64::consts::PI, io::{stdout, Write}};
 
 use libc::{ioctl, winsize, STDOUT_FILENO, TIOCGWINSZ};
-use ray_trace::{
-    camera::Camera,
-    hit::HittableList,
-    material::materials::{Dielectric, Lambertian, Metal},
-    model::sphere::Sphere,
-    vec3::Vec3,
-};
+use ray_trace::{camera::CameraBuilder, scenes::random_spheres_world, vec3::Vec3};
 
 fn get_terminal_size() -> Option<(u16, u16)> {
     unsafe {
@@ -26,80 +20,27 @@ fn clear_screen() {
 }
 
 fn main() {
-    let mut world = HittableList::new();
-
-    let material_ground = Lambertian::new(Vec3::new(0.5, 0.5, 0.5));
-    world.add(Box::new(Sphere::new(
-        Vec3::new(0.0, -1000.0, 0.0),
-        1000.0,
-        material_ground.clone(),
-    )));
-    for a in -11..11 {
-        for b in -11..11 {
-            let choose_mat = rand::random::<f64>();
-            let center = Vec3::new(
-                a as f64 + 0.9 * rand::random::<f64>(),
-                0.2,
-                b as f64 + 0.9 * rand::random::<f64>(),
-            );
-
-            if (center - Vec3::new(4.0, 0.2, 0.0)).length() > 0.9 {
-                if choose_mat < 0.8 {
-                    let albedo = Vec3::random_vec() * Vec3::random_vec();
-                    let sphere_material = Lambertian::new(albedo);
-                    world.add(Box::new(Sphere::new(center, 0.2, sphere_material.clone())));
-                } else if choose_mat < 0.95 {
-                    let albedo = Vec3::random(0.5, 1.0);
-                    let fuzz = rand::random::<f64>() * 0.5;
-                    let sphere_material = Metal::new(albedo, fuzz);
-                    world.add(Box::new(Sphere::new(center, 0.2, sphere_material.clone())));
-                } else {
-                    let sphere_material = Dielectric::new(1.5);
-                    world.add(Box::new(Sphere::new(center, 0.2, sphere_material.clone())));
-                }
-            }
-        }
-    }
-
-    let material_left = Dielectric::new(1.5);
-    world.add(Box::new(Sphere::new(
-        Vec3::new(0.0, 1.0, 0.0),
-        1.0,
-        material_left.clone(),
-    )));
-    let material_center = Lambertian::new(Vec3::new(0.4, 0.2, 0.1));
-    world.add(Box::new(Sphere::new(
-        Vec3::new(-4.0, 1.0, 0.0),
-        1.0,
-        material_center.clone(),
-    )));
-    let material_right = Metal::new(Vec3::new(0.7, 0.6, 0.5), 0.0);
-    world.add(Box::new(Sphere::new(
-        Vec3::new(4.0, 1.0, 0.0),
-        1.0,
-        material_right.clone(),
-    )));
+    let world = random_spheres_world();
 
     let mut angle: f64 = 0.0;
-    while true {
+    loop {
 
         let image_size = get_terminal_size().unwrap();
         let image_size = (image_size.0 as u32 / 2, image_size.1 as u32 - 2_u32); 
 
         let start_time = std::time::Instant::now();
-        let mut camera = Camera::new(
-            image_size,
-            "",
-            5,
-            10,
-            40.0,
-            (
-                Vec3::new(10.0 * angle.cos(), 2.0, 10.0 * angle.sin()),
-                Vec3::new(0.0, 0.0, 0.0),
-                Vec3::new(0.0, 1.0, 0.0),
-            ),
-            (0.6, 10.0),
-        );
+        let mut camera = CameraBuilder::new()
+            .image_size(image_size.0, image_size.1)
+            .output("")
+            .max_depth(5)
+            .samples(10)
+            .vfov(40.0)
+            .look_from(Vec3::new(10.0 * angle.cos(), 2.0, 10.0 * angle.sin()))
+            .look_at(Vec3::new(0.0, 0.0, 0.0))
+            .aperture(0.6)
+            .focus_distance(10.0)
+            .build()
+            .expect("terminal too small to render into");
         camera.disable_progress_bar();
         camera.render(&world, true);
         let elapsed = start_time.elapsed().as_millis();
@@ -0,0 +1,145 @@
+use std::cmp::Ordering;
+
+use crate::{
+    aabb::Aabb,
+    hit::{HitRecord, Hittable, HittableList},
+    interval::Interval,
+    ray::Ray,
+};
+
+// relative costs used by the surface area heuristic
+const TRAVERSAL_COST: f64 = 1.0;
+const INTERSECTION_COST: f64 = 1.0;
+const MAX_LEAF_SIZE: usize = 4;
+
+enum BvhChildren {
+    Leaf(HittableList),
+    Split(Box<BvhNode>, Box<BvhNode>),
+}
+
+/// Bounding volume hierarchy over the objects of a `HittableList`.
+///
+/// Splits are chosen with the surface area heuristic: every object boundary
+/// along each axis is a candidate, and the one minimizing the expected
+/// intersection cost wins. Nodes become leaves when splitting would not pay off.
+pub struct BvhNode {
+    bbox: Aabb,
+    children: BvhChildren,
+}
+
+impl BvhNode {
+    pub fn new(list: HittableList) -> Self {
+        let items = list
+            .objects
+            .into_iter()
+            .map(|object| {
+                let bbox = object.bounding_box();
+                (object, bbox)
+            })
+            .collect();
+        BvhNode::build(items)
+    }
+
+    fn build(mut items: Vec<(Box<dyn Hittable>, Aabb)>) -> Self {
+        let bbox = items
+            .iter()
+            .fold(Aabb::new_empty(), |acc, (_, b)| Aabb::new_enclosing(&acc, b));
+        let count = items.len();
+
+        if count <= 1 {
+            return BvhNode::leaf(items, bbox);
+        }
+
+        // (axis, number of objects on the left, cost)
+        let mut best: Option<(usize, usize, f64)> = None;
+        for axis in 0..3 {
+            sort_by_axis(&mut items, axis);
+
+            let mut right_area = vec![0.0; count];
+            let mut right_box = Aabb::new_empty();
+            for i in (1..count).rev() {
+                right_box = Aabb::new_enclosing(&right_box, &items[i].1);
+                right_area[i] = right_box.surface_area();
+            }
+
+            let mut left_box = Aabb::new_empty();
+            for i in 0..count - 1 {
+                left_box = Aabb::new_enclosing(&left_box, &items[i].1);
+                let left_count = i + 1;
+                let cost = left_box.surface_area() * left_count as f64
+                    + right_area[i + 1] * (count - left_count) as f64;
+                if best.is_none_or(|(_, _, best_cost)| cost < best_cost) {
+                    best = Some((axis, left_count, cost));
+                }
+            }
+        }
+
+        let leaf_cost = INTERSECTION_COST * count as f64;
+        let (axis, left_count) = match best {
+            Some((axis, left_count, cost)) => {
+                let split_cost = TRAVERSAL_COST + INTERSECTION_COST * cost / bbox.surface_area();
+                if split_cost.is_finite() {
+                    if count <= MAX_LEAF_SIZE && leaf_cost <= split_cost {
+                        return BvhNode::leaf(items, bbox);
+                    }
+                    (axis, left_count)
+                } else {
+                    // unbounded objects make every cost infinite, fall back to a median split
+                    (bbox.longest_axis(), count / 2)
+                }
+            }
+            None => (bbox.longest_axis(), count / 2),
+        };
+
+        sort_by_axis(&mut items, axis);
+        let right_items = items.split_off(left_count);
+        let left = BvhNode::build(items);
+        let right = BvhNode::build(right_items);
+
+        BvhNode {
+            bbox,
+            children: BvhChildren::Split(Box::new(left), Box::new(right)),
+        }
+    }
+
+    fn leaf(items: Vec<(Box<dyn Hittable>, Aabb)>, bbox: Aabb) -> Self {
+        let mut list = HittableList::new();
+        for (object, _) in items {
+            list.add(object);
+        }
+        BvhNode {
+            bbox,
+            children: BvhChildren::Leaf(list),
+        }
+    }
+}
+
+fn sort_by_axis(items: &mut [(Box<dyn Hittable>, Aabb)], axis: usize) {
+    items.sort_by(|a, b| {
+        a.1.centroid(axis)
+            .partial_cmp(&b.1.centroid(axis))
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
+impl Hittable for BvhNode {
+    fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord> {
+        if !self.bbox.hit(ray, interval) {
+            return None;
+        }
+
+        match &self.children {
+            BvhChildren::Leaf(list) => list.hit(ray, interval),
+            BvhChildren::Split(left, right) => {
+                let hit_left = left.hit(ray, interval);
+                let closest_so_far = hit_left.as_ref().map_or(interval.max, |record| record.t);
+                let hit_right = right.hit(ray, &Interval::new(interval.min, closest_so_far));
+                hit_right.or(hit_left)
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+}
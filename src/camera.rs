@@ -1,9 +1,18 @@
-use image::{ImageBuffer, Rgb};
+use std::{fmt::Display, sync::Arc};
+
+use image::{ImageBuffer, ImageError, Rgb};
 
 use crate::{
+    aperture::Aperture,
+    environment::{Environment, SkyGradient},
     hit::HittableList,
-    render::{multithread_render, singlethread_render},
+    integrator::{Integrator, PathTracer},
+    postprocess::PostProcess,
+    projection::Projection,
+    render::{focus_distance, multithread_render, singlethread_render},
+    stereo::Stereo,
     vec3::Vec3,
+    writer::{write_exr, write_pfm, OutputFormat},
 };
 
 pub struct CameraConfig {
@@ -20,6 +29,68 @@ pub struct CameraConfig {
     pub defocus_v: Vec3,
     pub defocus_angle: f64,
     pub disable_progress_bar: bool,
+    pub environment: Arc<dyn Environment>,
+    pub seed: Option<u64>,
+    // rays are cast at random times between shutter open and close
+    pub shutter: (f64, f64),
+    // emitters sampled directly at every diffuse bounce, see `integrator::PathTracer`
+    pub lights: Arc<HittableList>,
+    // bounces before Russian roulette may end a path, `None` disables it
+    pub russian_roulette: Option<u32>,
+    pub integrator: Arc<dyn Integrator>,
+    pub projection: Projection,
+    // camera space axes in the world: right, up and backwards
+    pub basis: (Vec3, Vec3, Vec3),
+    pub focus_dist: f64,
+    // left and right eye views side by side or stacked, `None` for a single view
+    pub stereo: Option<Stereo>,
+    pub aperture: Aperture,
+    // how far the lens barrel cuts into the aperture towards the frame edges
+    pub vignetting: f64,
+    // Image point focused on by a ray cast before each render, (0, 0) is the
+    // top left corner. `focus_dist` is used when nothing is seen there
+    pub autofocus: Option<(f64, f64)>,
+}
+
+impl CameraConfig {
+    // size of the image one view covers, half of it for each eye of a stereo pair
+    pub fn view_size(&self) -> (u32, u32) {
+        match &self.stereo {
+            Some(stereo) => stereo.eye_size(self.image_size),
+            None => self.image_size,
+        }
+    }
+
+    // pixel grid on the focus plane, spans the view of a perspective or
+    // orthographic projection
+    fn place_viewport(&mut self) {
+        let (width, height) = self.view_size();
+        let (u, v, w) = self.basis;
+        let viewport_height = match self.projection {
+            Projection::Orthographic { height } => height,
+            _ => 2.0 * (self.vfov.to_radians() / 2.0).tan() * self.focus_dist,
+        };
+        let viewport_width = width as f64 / height as f64 * viewport_height;
+
+        let viewport_u = u * viewport_width;
+        let viewport_v = v * -viewport_height;
+        self.pixel_delta_u = viewport_u / width as f64;
+        self.pixel_delta_v = viewport_v / height as f64;
+        let viewport_upper_left =
+            self.center - w * self.focus_dist - viewport_u / 2.0 - viewport_v / 2.0;
+        self.pixel00_loc =
+            viewport_upper_left + self.pixel_delta_u / 2.0 + self.pixel_delta_v / 2.0;
+    }
+
+    // moves the focus plane, the defocus angle is kept
+    fn focus_at(&mut self, focus_dist: f64) {
+        let defocus_radius = focus_dist * (self.defocus_angle.to_radians() / 2.0).tan();
+        let (u, v, _) = self.basis;
+        self.defocus_u = u * defocus_radius;
+        self.defocus_v = v * defocus_radius;
+        self.focus_dist = focus_dist;
+        self.place_viewport();
+    }
 }
 
 impl Clone for CameraConfig {
@@ -38,14 +109,337 @@ impl Clone for CameraConfig {
             defocus_v: self.defocus_v,
             defocus_angle: self.defocus_angle,
             disable_progress_bar: self.disable_progress_bar,
+            environment: Arc::clone(&self.environment),
+            seed: self.seed,
+            shutter: self.shutter,
+            lights: Arc::clone(&self.lights),
+            russian_roulette: self.russian_roulette,
+            integrator: Arc::clone(&self.integrator),
+            projection: self.projection,
+            basis: self.basis,
+            focus_dist: self.focus_dist,
+            stereo: self.stereo,
+            aperture: self.aperture.clone(),
+            vignetting: self.vignetting,
+            autofocus: self.autofocus,
+        }
+    }
+}
+
+/// Arguments of `Camera::new`, kept around so they can be adjusted before
+/// the camera is built.
+#[derive(Debug, Clone)]
+pub struct CameraSettings {
+    pub image_size: (u32, u32),
+    pub filepath: String,
+    pub max_depth: u32,
+    pub samples_per_pixel: u32,
+    pub vfov: f64,
+    pub look_from: Vec3,
+    pub look_at: Vec3,
+    pub vup: Vec3,
+    pub defocus_angle: f64,
+    pub focus_dist: f64,
+    pub shutter: (f64, f64),
+    pub russian_roulette: Option<u32>,
+    pub projection: Projection,
+    pub stereo: Option<Stereo>,
+    pub aperture: Aperture,
+    pub vignetting: f64,
+    // `focus_dist` is the fallback when the autofocus ray misses
+    pub autofocus: Option<(f64, f64)>,
+}
+
+impl CameraSettings {
+    pub fn build(&self) -> Result<Camera, CameraError> {
+        CameraBuilder::from(self.clone()).build()
+    }
+}
+
+/// Why a camera could not be built.
+#[derive(Debug, Clone)]
+pub enum CameraError {
+    ZeroImageSize((u32, u32)),
+    ZeroSamples,
+    LookFromIsLookAt(Vec3),
+    VupParallelToView(Vec3),
+    InvalidFieldOfView(f64),
+    InvalidAperture(f64),
+    InvalidApertureShape(Aperture),
+    NonPositiveFocusDistance(f64),
+    InvalidShutter((f64, f64)),
+    InvalidProjection(Projection),
+    InvalidStereo(Stereo),
+    InvalidVignetting(f64),
+    InvalidAutofocus((f64, f64)),
+    InvalidRussianRoulette((u32, u32)),
+}
+
+impl Display for CameraError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CameraError::ZeroImageSize((width, height)) => {
+                write!(f, "image size {}x{} is empty, both sides must be positive", width, height)
+            }
+            CameraError::ZeroSamples => write!(f, "samples per pixel must be positive"),
+            CameraError::LookFromIsLookAt(point) => write!(
+                f,
+                "look_from and look_at are both {}, the camera has no view direction",
+                point
+            ),
+            CameraError::VupParallelToView(vup) => write!(
+                f,
+                "vup {} is zero or parallel to the view direction, it cannot tell which way is up",
+                vup
+            ),
+            CameraError::InvalidFieldOfView(vfov) => {
+                write!(f, "vertical field of view {} must be within (0, 180) degrees", vfov)
+            }
+            CameraError::InvalidAperture(angle) => {
+                write!(f, "aperture (defocus angle) {} must be within [0, 180) degrees", angle)
+            }
+            CameraError::InvalidApertureShape(aperture) => match aperture {
+                Aperture::Polygon { blades, rotation } => write!(
+                    f,
+                    "aperture polygon with {} blades turned by {} degrees needs at least 3 \
+                     blades and a finite rotation",
+                    blades, rotation
+                ),
+                other => write!(f, "invalid aperture {:?}", other),
+            },
+            CameraError::NonPositiveFocusDistance(dist) => {
+                write!(f, "focus distance {} must be positive", dist)
+            }
+            CameraError::InvalidShutter((open, close)) => {
+                write!(f, "shutter closes at {} before it opens at {}", close, open)
+            }
+            CameraError::InvalidProjection(projection) => match projection {
+                Projection::Orthographic { height } => {
+                    write!(f, "orthographic view height {} must be positive", height)
+                }
+                Projection::Fisheye { fov } => {
+                    write!(f, "fisheye field of view {} must be within (0, 360] degrees", fov)
+                }
+                other => write!(f, "invalid projection {:?}", other),
+            },
+            CameraError::InvalidStereo(stereo) => write!(
+                f,
+                "stereo interpupillary distance {} must not be negative and convergence \
+                 distance {:?} must be positive",
+                stereo.ipd, stereo.convergence
+            ),
+            CameraError::InvalidVignetting(strength) => {
+                write!(f, "vignetting {} must not be negative", strength)
+            }
+            CameraError::InvalidAutofocus((s, t)) => write!(
+                f,
+                "autofocus point ({}, {}) lies outside the image, both must be within [0, 1]",
+                s, t
+            ),
+            CameraError::InvalidRussianRoulette((min_depth, max_depth)) => write!(
+                f,
+                "Russian roulette after {} bounces never starts, max_depth is {}",
+                min_depth, max_depth
+            ),
         }
     }
 }
 
+impl std::error::Error for CameraError {}
+
+fn check_focus_dist(focus_dist: f64) -> Result<(), CameraError> {
+    if focus_dist > 0.0 && focus_dist.is_finite() {
+        Ok(())
+    } else {
+        Err(CameraError::NonPositiveFocusDistance(focus_dist))
+    }
+}
+
+/// Sets up a camera by name instead of through the positional arguments of
+/// `Camera::new`. `build` checks the values and explains what is wrong.
+///
+/// Defaults: a 400x225 image written to `output.png`, 100 samples per
+/// pixel, 50 bounces, a 90 degree field of view from the origin down -z,
+/// a pinhole lens focused on `look_at` and no autofocus.
+#[derive(Debug, Clone)]
+pub struct CameraBuilder {
+    settings: CameraSettings,
+    // distance to `look_at` unless set
+    focus_dist: Option<f64>,
+}
+
+impl Default for CameraBuilder {
+    fn default() -> Self {
+        CameraBuilder {
+            settings: CameraSettings {
+                image_size: (400, 225),
+                filepath: "output.png".to_string(),
+                max_depth: 50,
+                samples_per_pixel: 100,
+                vfov: 90.0,
+                look_from: Vec3::new_zero(),
+                look_at: Vec3::new(0.0, 0.0, -1.0),
+                vup: Vec3::new(0.0, 1.0, 0.0),
+                defocus_angle: 0.0,
+                focus_dist: 1.0,
+                shutter: (0.0, 0.0),
+                russian_roulette: None,
+                projection: Projection::Perspective,
+                stereo: None,
+                aperture: Aperture::Circle,
+                vignetting: 0.0,
+                autofocus: None,
+            },
+            focus_dist: None,
+        }
+    }
+}
+
+impl From<CameraSettings> for CameraBuilder {
+    fn from(settings: CameraSettings) -> Self {
+        let focus_dist = Some(settings.focus_dist);
+        CameraBuilder {
+            settings,
+            focus_dist,
+        }
+    }
+}
+
+impl CameraBuilder {
+    pub fn new() -> Self {
+        CameraBuilder::default()
+    }
+
+    pub fn image_size(mut self, width: u32, height: u32) -> Self {
+        self.settings.image_size = (width, height);
+        self
+    }
+
+    pub fn look_from(mut self, look_from: Vec3) -> Self {
+        self.settings.look_from = look_from;
+        self
+    }
+
+    pub fn look_at(mut self, look_at: Vec3) -> Self {
+        self.settings.look_at = look_at;
+        self
+    }
+
+    pub fn vup(mut self, vup: Vec3) -> Self {
+        self.settings.vup = vup;
+        self
+    }
+
+    // vertical field of view in degrees
+    pub fn vfov(mut self, vfov: f64) -> Self {
+        self.settings.vfov = vfov;
+        self
+    }
+
+    // angle in degrees of the cone from the focus plane to the lens, 0 for a pinhole
+    pub fn aperture(mut self, defocus_angle: f64) -> Self {
+        self.settings.defocus_angle = defocus_angle;
+        self
+    }
+
+    pub fn aperture_shape(mut self, aperture: Aperture) -> Self {
+        self.settings.aperture = aperture;
+        self
+    }
+
+    pub fn focus_distance(mut self, focus_dist: f64) -> Self {
+        self.focus_dist = Some(focus_dist);
+        self
+    }
+
+    pub fn samples(mut self, samples_per_pixel: u32) -> Self {
+        self.settings.samples_per_pixel = samples_per_pixel;
+        self
+    }
+
+    pub fn max_depth(mut self, max_depth: u32) -> Self {
+        self.settings.max_depth = max_depth;
+        self
+    }
+
+    pub fn output(mut self, filepath: &str) -> Self {
+        self.settings.filepath = filepath.to_string();
+        self
+    }
+
+    pub fn shutter(mut self, open: f64, close: f64) -> Self {
+        self.settings.shutter = (open, close);
+        self
+    }
+
+    pub fn russian_roulette(mut self, min_depth: u32) -> Self {
+        self.settings.russian_roulette = Some(min_depth);
+        self
+    }
+
+    pub fn projection(mut self, projection: Projection) -> Self {
+        self.settings.projection = projection;
+        self
+    }
+
+    pub fn stereo(mut self, stereo: Stereo) -> Self {
+        self.settings.stereo = Some(stereo);
+        self
+    }
+
+    pub fn vignetting(mut self, strength: f64) -> Self {
+        self.settings.vignetting = strength;
+        self
+    }
+
+    // Focus on what is seen at `point`, (0.5, 0.5) is the image center. The
+    // focus distance is only used when nothing is there.
+    pub fn autofocus(mut self, point: (f64, f64)) -> Self {
+        self.settings.autofocus = Some(point);
+        self
+    }
+
+    pub fn build(&self) -> Result<Camera, CameraError> {
+        let settings = &self.settings;
+        let focus_dist = self
+            .focus_dist
+            .unwrap_or_else(|| (settings.look_from - settings.look_at).length());
+        // `Camera::new` and the setters check the values
+        let mut camera = Camera::new(
+            settings.image_size,
+            &settings.filepath,
+            settings.max_depth,
+            settings.samples_per_pixel,
+            settings.vfov,
+            (settings.look_from, settings.look_at, settings.vup),
+            (settings.defocus_angle, focus_dist),
+        )?;
+        let (open, close) = settings.shutter;
+        camera.set_shutter(open, close)?;
+        camera.set_projection(settings.projection)?;
+        if let Some(stereo) = settings.stereo {
+            camera.set_stereo(stereo)?;
+        }
+        camera.set_aperture(settings.aperture.clone())?;
+        camera.set_vignetting(settings.vignetting)?;
+        if let Some(min_depth) = settings.russian_roulette {
+            camera.set_russian_roulette(min_depth)?;
+        }
+        if let Some(point) = settings.autofocus {
+            camera.set_autofocus(point)?;
+        }
+        Ok(camera)
+    }
+}
+
 pub struct Camera {
     output_handler: ImageBuffer<Rgb<u8>, Vec<u8>>,
     pub filepath: String,
+    // linear radiance as rendered
+    pub film: Vec<Vec<Vec3>>,
+    // film after post processing, what gets written to disk
     pub image: Vec<Vec<[u8; 3]>>,
+    pub post_process: PostProcess,
     pub config: CameraConfig,
 }
 
@@ -58,48 +452,58 @@ impl Camera {
         vfov: f64,
         view_position: (Vec3, Vec3, Vec3),
         defocus: (f64, f64)
-    ) -> Self {
+    ) -> Result<Self, CameraError> {
         let (width, height) = image_size;
         let (lookfrom, lookat, vup) = (view_position.0, view_position.1, view_position.2);
         let (defocus_angle, focus_dist) = (defocus.0, defocus.1);
 
-        assert!(width > 0 && height > 0);
+        if width == 0 || height == 0 {
+            return Err(CameraError::ZeroImageSize(image_size));
+        }
+        if samples_per_pixel == 0 {
+            return Err(CameraError::ZeroSamples);
+        }
+        let view = lookfrom - lookat;
+        if view.near_zero() {
+            return Err(CameraError::LookFromIsLookAt(lookfrom));
+        }
+        if vup.cross(&view).near_zero() {
+            return Err(CameraError::VupParallelToView(vup));
+        }
+        if !(vfov > 0.0 && vfov < 180.0) {
+            return Err(CameraError::InvalidFieldOfView(vfov));
+        }
+        if !(0.0..180.0).contains(&defocus_angle) {
+            return Err(CameraError::InvalidAperture(defocus_angle));
+        }
+        check_focus_dist(focus_dist)?;
 
         let aspect_ratio = width as f64 / height as f64;
         let center = lookfrom;
-        let theta = vfov.to_radians();
-        let h = (theta / 2.0).tan();
-        let viewport_height = 2.0 * h * focus_dist;
-        let viewport_width = aspect_ratio * viewport_height;
 
         let w = (lookfrom - lookat).unit();
         let u = vup.cross(&w).unit();
         let v = w.cross(&u);
 
-        let viewport_u = u * viewport_width;
-        let viewport_v = v * -viewport_height;
-        let pixel_delta_u = viewport_u / width as f64;
-        let pixel_delta_v = viewport_v / height as f64;
-        let viewport_upper_left =
-            center - w * focus_dist - viewport_u / 2.0 - viewport_v / 2.0;
-        let pixel00_loc = viewport_upper_left + pixel_delta_u / 2.0 + pixel_delta_v / 2.0;
-
         let output_handler = ImageBuffer::new(width, height);
         let filepath = filepath.to_string();
+        let film = vec![vec![Vec3::new_zero(); width as usize]; height as usize];
         let image = vec![vec![[0u8; 3]; width as usize]; height as usize];
 
         let defocus_radius = focus_dist * (defocus_angle.to_radians() / 2.0).tan();
         let defocus_u = u * defocus_radius;
         let defocus_v = v * defocus_radius;
 
-        Camera {
+        let mut camera = Camera {
             output_handler,
             filepath,
+            film,
             image,
+            post_process: PostProcess::default(),
             config: CameraConfig {
-                pixel00_loc,
-                pixel_delta_u,
-                pixel_delta_v,
+                pixel00_loc: Vec3::new_zero(),
+                pixel_delta_u: Vec3::new_zero(),
+                pixel_delta_v: Vec3::new_zero(),
                 samples_per_pixel,
                 center,
                 max_depth: depth,
@@ -110,30 +514,188 @@ impl Camera {
                 defocus_v,
                 defocus_angle,
                 disable_progress_bar: false,
+                environment: SkyGradient::default_sky(),
+                seed: None,
+                shutter: (0.0, 0.0),
+                lights: Arc::new(HittableList::new()),
+                russian_roulette: None,
+                integrator: PathTracer::new(),
+                projection: Projection::Perspective,
+                basis: (u, v, w),
+                focus_dist,
+                stereo: None,
+                aperture: Aperture::Circle,
+                vignetting: 0.0,
+                autofocus: None,
             },
-        }
+        };
+        camera.config.place_viewport();
+        Ok(camera)
     }
 
     pub fn disable_progress_bar(&mut self) {
         self.config.disable_progress_bar = true;
     }
 
+    // makes renders reproducible, pixels are sampled from a generator seeded per pixel
+    pub fn set_seed(&mut self, seed: u64) {
+        self.config.seed = Some(seed);
+    }
+
+    // moving objects travel between time 0 and 1, an open shutter blurs them
+    pub fn set_shutter(&mut self, open: f64, close: f64) -> Result<(), CameraError> {
+        if close < open || open.is_nan() || close.is_nan() {
+            return Err(CameraError::InvalidShutter((open, close)));
+        }
+        self.config.shutter = (open, close);
+        Ok(())
+    }
+
+    // Objects in `lights` are sampled directly and combined with BSDF
+    // sampling through MIS. They must also be part of the world, share them
+    // with an `Arc` to avoid building them twice.
+    pub fn set_lights(&mut self, lights: HittableList) {
+        self.config.lights = Arc::new(lights);
+    }
+
+    // Paths deeper than `min_depth` bounces are ended at random depending on
+    // their throughput. Unbiased, so `max_depth` can be raised freely.
+    pub fn set_russian_roulette(&mut self, min_depth: u32) -> Result<(), CameraError> {
+        if min_depth >= self.config.max_depth {
+            return Err(CameraError::InvalidRussianRoulette((min_depth, self.config.max_depth)));
+        }
+        self.config.russian_roulette = Some(min_depth);
+        Ok(())
+    }
+
+    // the algorithm that estimates each sample, the path tracer by default
+    pub fn set_integrator(&mut self, integrator: Arc<dyn Integrator>) {
+        self.config.integrator = integrator;
+    }
+
+    pub fn set_projection(&mut self, projection: Projection) -> Result<(), CameraError> {
+        let valid = match projection {
+            Projection::Orthographic { height } => height > 0.0 && height.is_finite(),
+            Projection::Fisheye { fov } => fov > 0.0 && fov <= 360.0,
+            _ => true,
+        };
+        if !valid {
+            return Err(CameraError::InvalidProjection(projection));
+        }
+        self.config.projection = projection;
+        self.config.place_viewport();
+        Ok(())
+    }
+
+    // The image holds both eye views, so each gets half of its width or height.
+    pub fn set_stereo(&mut self, stereo: Stereo) -> Result<(), CameraError> {
+        self.config.stereo = Some(stereo.validate()?);
+        self.config.place_viewport();
+        Ok(())
+    }
+
+    // shape of the out of focus blur, only visible with a defocus angle
+    pub fn set_aperture(&mut self, aperture: Aperture) -> Result<(), CameraError> {
+        if let Aperture::Polygon { blades, rotation } = aperture {
+            if blades < 3 || !rotation.is_finite() {
+                return Err(CameraError::InvalidApertureShape(aperture));
+            }
+        }
+        self.config.aperture = aperture;
+        Ok(())
+    }
+
+    // Optical vignetting, cat's eye bokeh and darker corners. At 1 the lens
+    // barrel hides half of the aperture width in the corners of the frame.
+    pub fn set_vignetting(&mut self, strength: f64) -> Result<(), CameraError> {
+        if strength < 0.0 || !strength.is_finite() {
+            return Err(CameraError::InvalidVignetting(strength));
+        }
+        self.config.vignetting = strength;
+        Ok(())
+    }
+
+    pub fn set_environment(&mut self, environment: Arc<dyn Environment>) {
+        self.config.environment = environment;
+    }
+
+    // Focus on the scene at `point` before every render. `focus_dist` stays
+    // as it is and is used when the autofocus ray finds nothing.
+    pub fn set_autofocus(&mut self, point: (f64, f64)) -> Result<(), CameraError> {
+        let (s, t) = point;
+        if !((0.0..=1.0).contains(&s) && (0.0..=1.0).contains(&t)) {
+            return Err(CameraError::InvalidAutofocus(point));
+        }
+        self.config.autofocus = Some(point);
+        Ok(())
+    }
+
+    // moves the focus plane, the defocus angle is kept
+    pub fn set_focus_dist(&mut self, focus_dist: f64) -> Result<(), CameraError> {
+        check_focus_dist(focus_dist)?;
+        self.config.focus_at(focus_dist);
+        Ok(())
+    }
+
+    // Distance the autofocus ray finds against `world`, the focus distance
+    // when autofocus is off or the ray sees nothing.
+    pub fn autofocus(&self, world: &HittableList) -> f64 {
+        self.config
+            .autofocus
+            .and_then(|(s, t)| focus_distance(s, t, world, &self.config))
+            .filter(|dist| *dist > 0.0 && dist.is_finite())
+            .unwrap_or(self.config.focus_dist)
+    }
+
+    // The config a render uses, focused by autofocus. The camera keeps its
+    // own focus distance, so the next render starts from it again.
+    pub fn focused_config(&self, world: &HittableList) -> CameraConfig {
+        let mut config = self.config.clone();
+        if config.autofocus.is_some() {
+            config.focus_at(self.autofocus(world));
+        }
+        config
+    }
+
     pub fn render(&mut self, world: &HittableList, multithread: bool) {
+        let config = self.focused_config(world);
         if multithread {
-            self.image = multithread_render(world, &self.config);
+            self.film = multithread_render(world, &config);
         } else {
-            self.image = singlethread_render(world, &self.config)
+            self.film = singlethread_render(world, &config)
         }
+        self.develop();
+    }
 
+    pub fn set_post_process(&mut self, post_process: PostProcess) {
+        self.post_process = post_process;
+        self.develop();
     }
 
+    // re-run post processing on the current film, no re-render needed
+    pub fn develop(&mut self) {
+        self.image = self.post_process.develop(&self.film);
+    }
+
+    // format is picked from the extension of `filepath`
     pub fn output(&mut self) {
-        for row in 0..self.config.image_size.1 {
-            for col in 0..self.config.image_size.0 {
-                self.output_handler
-                    .put_pixel(col, row, Rgb(self.image[row as usize][col as usize]));
+        let filepath = self.filepath.clone();
+        self.output_as(&filepath, OutputFormat::from_path(&filepath)).unwrap();
+    }
+
+    pub fn output_as(&mut self, filepath: &str, format: OutputFormat) -> Result<(), ImageError> {
+        match format {
+            OutputFormat::Ldr => {
+                for row in 0..self.config.image_size.1 {
+                    for col in 0..self.config.image_size.0 {
+                        self.output_handler
+                            .put_pixel(col, row, Rgb(self.image[row as usize][col as usize]));
+                    }
+                }
+                self.output_handler.save(filepath)
             }
+            OutputFormat::Exr => write_exr(filepath, &self.film),
+            OutputFormat::Pfm => Ok(write_pfm(filepath, &self.film)?),
         }
-        self.output_handler.save(&self.filepath).unwrap();
     }
 }
@@ -0,0 +1,120 @@
+use std::{f64::consts::PI, path::Path, sync::Arc};
+
+use image::ImageError;
+
+use crate::{ray::Ray, vec3::Vec3};
+
+/// Radiance arriving from infinitely far away, seen by rays that miss the scene.
+pub trait Environment: Send + Sync {
+    fn color(&self, ray: &Ray) -> Vec3;
+}
+
+pub struct SolidBackground {
+    pub color: Vec3,
+}
+
+impl SolidBackground {
+    pub fn new(color: Vec3) -> Arc<Self> {
+        Arc::new(SolidBackground { color })
+    }
+
+    pub fn black() -> Arc<Self> {
+        SolidBackground::new(Vec3::new_zero())
+    }
+}
+
+impl Environment for SolidBackground {
+    fn color(&self, ray: &Ray) -> Vec3 {
+        let _ = ray;
+        self.color
+    }
+}
+
+pub struct SkyGradient {
+    pub horizon: Vec3,
+    pub zenith: Vec3,
+}
+
+impl SkyGradient {
+    pub fn new(horizon: Vec3, zenith: Vec3) -> Arc<Self> {
+        Arc::new(SkyGradient { horizon, zenith })
+    }
+
+    // white-to-blue sky used by the original renderer
+    pub fn default_sky() -> Arc<Self> {
+        SkyGradient::new(Vec3::new(1.0, 1.0, 1.0), Vec3::new(0.5, 0.7, 1.0))
+    }
+}
+
+impl Environment for SkyGradient {
+    fn color(&self, ray: &Ray) -> Vec3 {
+        let unit_direction = ray.direction.unit();
+        let a = 0.5 * (unit_direction.y + 1.0);
+        self.horizon * (1.0 - a) + self.zenith * a
+    }
+}
+
+/// Equirectangular (latitude/longitude) environment map, typically an HDR image.
+///
+/// The top row of the image maps to +y, and the horizontal center to +x.
+pub struct ImageEnvironment {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Vec3>,
+    pub intensity: f64,
+}
+
+impl ImageEnvironment {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Arc<Self>, ImageError> {
+        ImageEnvironment::load_scaled(path, 1.0)
+    }
+
+    // radiance of the map multiplied by `intensity`
+    pub fn load_scaled<P: AsRef<Path>>(path: P, intensity: f64) -> Result<Arc<Self>, ImageError> {
+        let image = image::open(path)?.into_rgb32f();
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let pixels = image
+            .pixels()
+            .map(|p| Vec3::new(p[0] as f64, p[1] as f64, p[2] as f64))
+            .collect();
+        Ok(Arc::new(ImageEnvironment {
+            width,
+            height,
+            pixels,
+            intensity,
+        }))
+    }
+
+    fn texel(&self, x: isize, y: isize) -> Vec3 {
+        // wrap around horizontally, clamp at the poles
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.clamp(0, self.height as isize - 1) as usize;
+        self.pixels[y * self.width + x]
+    }
+
+    fn bilinear(&self, u: f64, v: f64) -> Vec3 {
+        let x = u * self.width as f64 - 0.5;
+        let y = v * self.height as f64 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as isize, y0 as isize);
+
+        let top = self.texel(x0, y0) * (1.0 - fx) + self.texel(x0 + 1, y0) * fx;
+        let bottom = self.texel(x0, y0 + 1) * (1.0 - fx) + self.texel(x0 + 1, y0 + 1) * fx;
+        top * (1.0 - fy) + bottom * fy
+    }
+}
+
+impl Environment for ImageEnvironment {
+    fn color(&self, ray: &Ray) -> Vec3 {
+        if self.pixels.is_empty() {
+            return Vec3::new_zero();
+        }
+        let d = ray.direction.unit();
+        let phi = (-d.z).atan2(d.x) + PI;
+        let theta = (-d.y).clamp(-1.0, 1.0).acos();
+        let u = phi / (2.0 * PI);
+        let v = 1.0 - theta / PI;
+        self.bilinear(u, v) * self.intensity
+    }
+}
@@ -1,11 +1,16 @@
 use std::sync::Arc;
 
-use crate::{vec3::Vec3, ray::Ray, interval::Interval, material::materials::Material};
+use crate::{
+    aabb::Aabb, vec3::Vec3, ray::Ray, interval::Interval, material::materials::Material, rng::random,
+};
 
 pub struct HitRecord {
     pub p: Vec3,
     pub normal: Vec3,
     pub t: f64,
+    // surface coordinates of the hit point, used for texture lookup
+    pub u: f64,
+    pub v: f64,
     pub front_face: bool,
     pub material: Arc<dyn Material>
 }
@@ -16,12 +21,46 @@ pub struct HittableList {
 
 pub trait Hittable: Send + Sync {
     fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord>;
+    fn bounding_box(&self) -> Aabb;
+
+    // Sampling the object as a light: `random_direction` picks a direction
+    // from `origin` towards the object and `pdf_value` is the density (per
+    // solid angle) of that choice. Objects that cannot be sampled keep the
+    // defaults and must not be used as lights.
+    fn pdf_value(&self, origin: &Vec3, direction: &Vec3) -> f64 {
+        let _ = (origin, direction);
+        0.0
+    }
+
+    fn random_direction(&self, origin: &Vec3) -> Vec3 {
+        let _ = origin;
+        Vec3::new(1.0, 0.0, 0.0)
+    }
 }
 
 
+// lets shared objects (`Arc<dyn Hittable>`) go anywhere a hittable is expected
+impl<H: Hittable + ?Sized> Hittable for Arc<H> {
+    fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord> {
+        (**self).hit(ray, interval)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        (**self).bounding_box()
+    }
+
+    fn pdf_value(&self, origin: &Vec3, direction: &Vec3) -> f64 {
+        (**self).pdf_value(origin, direction)
+    }
+
+    fn random_direction(&self, origin: &Vec3) -> Vec3 {
+        (**self).random_direction(origin)
+    }
+}
+
 impl HitRecord {
     pub fn new(p: Vec3, normal: Vec3, t: f64, material: Arc<dyn Material>) -> Self {
-        HitRecord { p, normal, t, front_face: false, material }
+        HitRecord { p, normal, t, u: 0.0, v: 0.0, front_face: false, material }
     }
 
     pub fn set_face_normal(&mut self, ray: &Ray, outward_normal: &Vec3) {
@@ -44,8 +83,12 @@ impl HittableList {
     pub fn clear(&mut self) {
         self.objects.clear();
     }
+}
 
-    pub fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord> {
+// a list is a hittable itself, so groups of objects can be nested, transformed
+// and reused as a unit
+impl Hittable for HittableList {
+    fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord> {
         let mut hit_record: Option<HitRecord> = None;
         let mut closest_so_far = interval.max;
 
@@ -58,4 +101,32 @@ impl HittableList {
 
         hit_record
     }
+
+    fn bounding_box(&self) -> Aabb {
+        self.objects.iter().fold(Aabb::new_empty(), |bbox, object| {
+            Aabb::new_enclosing(&bbox, &object.bounding_box())
+        })
+    }
+
+    // a list of lights samples each of them with equal probability
+    fn pdf_value(&self, origin: &Vec3, direction: &Vec3) -> f64 {
+        if self.objects.is_empty() {
+            return 0.0;
+        }
+        let sum: f64 = self
+            .objects
+            .iter()
+            .map(|object| object.pdf_value(origin, direction))
+            .sum();
+        sum / self.objects.len() as f64
+    }
+
+    fn random_direction(&self, origin: &Vec3) -> Vec3 {
+        if self.objects.is_empty() {
+            return Vec3::new(1.0, 0.0, 0.0);
+        }
+        let count = self.objects.len();
+        let index = ((random::<f64>() * count as f64) as usize).min(count - 1);
+        self.objects[index].random_direction(origin)
+    }
 }
\ No newline at end of file
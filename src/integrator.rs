@@ -0,0 +1,284 @@
+use std::sync::Arc;
+
+use crate::{
+    camera::CameraConfig,
+    hit::{HitRecord, Hittable, HittableList},
+    interval::Interval,
+    ray::Ray,
+    rng::random,
+    vec3::Vec3,
+};
+
+/// Estimates the radiance arriving at the camera along a ray. Pixels average
+/// one estimate per sample.
+pub trait Integrator: Send + Sync {
+    fn radiance(&self, ray: Ray, world: &HittableList, config: &CameraConfig) -> Vec3;
+}
+
+fn first_hit(ray: &Ray, world: &HittableList) -> Option<HitRecord> {
+    world.hit(ray, &Interval::new(0.001, f64::INFINITY))
+}
+
+/// State carried from one bounce of a path to the next.
+pub struct PathState {
+    pub ray: Ray,
+    // product of the attenuations along the path so far
+    pub throughput: Vec3,
+    // MIS weight of emission found by `ray`, see `PathTracer`
+    pub emission_weight: f64,
+    // surfaces the path has scattered from
+    pub bounces: u32,
+    pub radiance: Vec3,
+}
+
+impl PathState {
+    pub fn new(ray: Ray) -> Self {
+        PathState {
+            ray,
+            throughput: Vec3::new(1.0, 1.0, 1.0),
+            emission_weight: 1.0,
+            bounces: 0,
+            radiance: Vec3::new_zero(),
+        }
+    }
+
+    // light reaching the camera through the path so far
+    pub fn add(&mut self, radiance: Vec3) {
+        self.radiance += self.throughput * radiance;
+    }
+
+    // Applies the attenuation of a bounce, then Russian roulette: once a path
+    // is `config.russian_roulette` bounces deep it survives with a
+    // probability that follows its throughput, survivors are scaled up so the
+    // estimate stays unbiased. Returns false when the path ends here.
+    pub fn attenuate(&mut self, attenuation: Vec3, config: &CameraConfig) -> bool {
+        self.throughput = self.throughput * attenuation;
+        match config.russian_roulette {
+            Some(min_depth) if self.bounces >= min_depth => {
+                let throughput = &self.throughput;
+                let survival = throughput.x.max(throughput.y).max(throughput.z).min(1.0);
+                if random::<f64>() >= survival {
+                    return false;
+                }
+                self.throughput /= survival;
+                true
+            }
+            _ => true,
+        }
+    }
+
+    pub fn advance(&mut self, ray: Ray, emission_weight: f64) {
+        self.ray = ray;
+        self.emission_weight = emission_weight;
+        self.bounces += 1;
+    }
+}
+
+// power heuristic with beta = 2
+fn mis_weight(pdf: f64, other_pdf: f64) -> f64 {
+    let (a, b) = (pdf * pdf, other_pdf * other_pdf);
+    if a + b > 0.0 { a / (a + b) } else { 0.0 }
+}
+
+// one direction sampled towards the lights, weighted against BSDF sampling
+fn sample_light(
+    ray: &Ray,
+    hit_record: &HitRecord,
+    attenuation: Vec3,
+    world: &HittableList,
+    lights: &HittableList,
+) -> Vec3 {
+    let material = &hit_record.material;
+    let direction = lights.random_direction(&hit_record.p);
+    let to_light = Ray::new_with_time(hit_record.p, direction, ray.time);
+    let light_pdf = lights.pdf_value(&hit_record.p, &direction);
+    let bsdf_pdf = material.scattering_pdf(ray, hit_record, &to_light);
+    if light_pdf <= 0.0 || bsdf_pdf <= 0.0 {
+        return Vec3::new_zero();
+    }
+    match first_hit(&to_light, world) {
+        Some(light_hit) => {
+            let radiance = light_hit.material.emitted(&light_hit);
+            radiance * attenuation * (bsdf_pdf / light_pdf) * mis_weight(light_pdf, bsdf_pdf)
+        }
+        None => Vec3::new_zero(),
+    }
+}
+
+// pure BSDF sampling
+fn trace(ray: Ray, max_depth: u32, world: &HittableList, config: &CameraConfig) -> Vec3 {
+    let mut path = PathState::new(ray);
+    while path.bounces < max_depth {
+        let Some(hit_record) = first_hit(&path.ray, world) else {
+            // background color
+            path.add(config.environment.color(&path.ray));
+            break;
+        };
+        path.add(hit_record.material.emitted(&hit_record));
+        let Some((attenutation, scatter)) = hit_record.material.scatter(&path.ray, &hit_record)
+        else {
+            break;
+        };
+        if !path.attenuate(attenutation, config) {
+            break;
+        }
+        path.advance(scatter, 1.0);
+    }
+    path.radiance
+}
+
+// At every non-specular bounce one direction is sampled towards
+// `config.lights` in addition to the BSDF sample, the two are combined with
+// multiple importance sampling.
+fn trace_mis(ray: Ray, max_depth: u32, world: &HittableList, config: &CameraConfig) -> Vec3 {
+    let lights = config.lights.as_ref();
+    let mut path = PathState::new(ray);
+    while path.bounces < max_depth {
+        let Some(hit_record) = first_hit(&path.ray, world) else {
+            path.add(config.environment.color(&path.ray));
+            break;
+        };
+
+        let material = &hit_record.material;
+        path.add(material.emitted(&hit_record) * path.emission_weight);
+        let Some((attenuation, scattered)) = material.scatter(&path.ray, &hit_record) else {
+            break;
+        };
+        let bsdf_pdf = material.scattering_pdf(&path.ray, &hit_record, &scattered);
+        if bsdf_pdf <= 0.0 {
+            // specular, only the scattered direction carries light
+            if !path.attenuate(attenuation, config) {
+                break;
+            }
+            path.advance(scattered, 1.0);
+            continue;
+        }
+
+        // light sample, skipped on the last bounce where the BSDF sample
+        // cannot reach a light either
+        if path.bounces + 1 < max_depth {
+            path.add(sample_light(&path.ray, &hit_record, attenuation, world, lights));
+        }
+
+        // the BSDF sample, emission it finds is weighted against light sampling
+        if !path.attenuate(attenuation, config) {
+            break;
+        }
+        let light_pdf = lights.pdf_value(&hit_record.p, &scattered.direction);
+        path.advance(scattered, mis_weight(bsdf_pdf, light_pdf));
+    }
+    path.radiance
+}
+
+/// Unidirectional path tracer, up to `max_depth` bounces. Adds next event
+/// estimation when the camera has lights to sample.
+pub struct PathTracer;
+
+impl PathTracer {
+    pub fn new() -> Arc<Self> {
+        Arc::new(PathTracer)
+    }
+}
+
+impl Integrator for PathTracer {
+    fn radiance(&self, ray: Ray, world: &HittableList, config: &CameraConfig) -> Vec3 {
+        if config.lights.objects.is_empty() {
+            trace(ray, config.max_depth, world, config)
+        } else {
+            trace_mis(ray, config.max_depth, world, config)
+        }
+    }
+}
+
+/// Emission plus light that reaches the first surface in one bounce,
+/// no interreflection.
+pub struct DirectLighting;
+
+impl DirectLighting {
+    pub fn new() -> Arc<Self> {
+        Arc::new(DirectLighting)
+    }
+}
+
+impl Integrator for DirectLighting {
+    fn radiance(&self, ray: Ray, world: &HittableList, config: &CameraConfig) -> Vec3 {
+        let max_depth = config.max_depth.min(2);
+        if config.lights.objects.is_empty() {
+            trace(ray, max_depth, world, config)
+        } else {
+            trace_mis(ray, max_depth, world, config)
+        }
+    }
+}
+
+/// Shading normal of the first hit mapped to a color, x/y/z to r/g/b.
+pub struct Normals;
+
+impl Normals {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Normals)
+    }
+}
+
+impl Integrator for Normals {
+    fn radiance(&self, ray: Ray, world: &HittableList, config: &CameraConfig) -> Vec3 {
+        match first_hit(&ray, world) {
+            Some(hit_record) => (hit_record.normal + Vec3::new(1.0, 1.0, 1.0)) * 0.5,
+            None => config.environment.color(&ray),
+        }
+    }
+}
+
+/// Surface color of the first hit without any lighting, emitters show their
+/// emission.
+pub struct Albedo;
+
+impl Albedo {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Albedo)
+    }
+}
+
+impl Integrator for Albedo {
+    fn radiance(&self, ray: Ray, world: &HittableList, config: &CameraConfig) -> Vec3 {
+        let Some(hit_record) = first_hit(&ray, world) else {
+            return config.environment.color(&ray);
+        };
+        match hit_record.material.scatter(&ray, &hit_record) {
+            Some((attenuation, _)) => attenuation,
+            None => hit_record.material.emitted(&hit_record),
+        }
+    }
+}
+
+/// Fraction of the hemisphere above the first hit that is open within
+/// `distance`, cosine weighted. One occlusion ray per sample.
+pub struct AmbientOcclusion {
+    pub distance: f64,
+}
+
+impl AmbientOcclusion {
+    pub fn new(distance: f64) -> Arc<Self> {
+        Arc::new(AmbientOcclusion { distance })
+    }
+}
+
+impl Integrator for AmbientOcclusion {
+    fn radiance(&self, ray: Ray, world: &HittableList, config: &CameraConfig) -> Vec3 {
+        let _ = config;
+        let Some(hit_record) = first_hit(&ray, world) else {
+            return Vec3::new(1.0, 1.0, 1.0);
+        };
+        let mut direction = hit_record.normal + Vec3::random_unit_vector();
+        if direction.near_zero() {
+            direction = hit_record.normal;
+        }
+        // unit direction, so `distance` is measured in world units
+        let occlusion_ray = Ray::new_with_time(hit_record.p, direction.unit(), ray.time);
+        if world.hit(&occlusion_ray, &Interval::new(0.001, self.distance)).is_some() {
+            Vec3::new_zero()
+        } else {
+            Vec3::new(1.0, 1.0, 1.0)
+        }
+    }
+}
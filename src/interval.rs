@@ -1,3 +1,4 @@
+#[derive(Debug, Clone, Copy)]
 pub struct Interval {
     pub min: f64,
     pub max: f64,
@@ -12,6 +13,19 @@ impl Interval {
         Interval { min: f64::INFINITY, max: f64::NEG_INFINITY }
     }
 
+    pub fn new_enclosing(a: &Interval, b: &Interval) -> Self {
+        Interval { min: a.min.min(b.min), max: a.max.max(b.max) }
+    }
+
+    pub fn size(&self) -> f64 {
+        self.max - self.min
+    }
+
+    pub fn expand(&self, delta: f64) -> Self {
+        let padding = delta / 2.0;
+        Interval { min: self.min - padding, max: self.max + padding }
+    }
+
     pub fn contains(&self, value: f64) -> bool {
         self.min <= value && value <= self.max
     }
@@ -32,4 +46,4 @@ impl Interval {
 }
 
 pub const EMPTY: Interval = Interval { min: f64::INFINITY, max: f64::NEG_INFINITY };
-pub const UNIVERSE: Interval = Interval { min: f64::NEG_INFINITY, max: f64::INFINITY };
\ No newline at end of file
+pub const UNIVERSE: Interval = Interval { min: f64::NEG_INFINITY, max: f64::INFINITY };
@@ -1,10 +1,22 @@
 pub mod vec3;
+pub mod transform;
+pub mod aabb;
+pub mod bvh;
 pub mod ray;
-pub mod color;
 pub mod writer;
 pub mod model;
 pub mod hit;
 pub mod interval;
+pub mod aperture;
 pub mod camera;
+pub mod projection;
+pub mod stereo;
 pub mod material;
-pub mod render;
\ No newline at end of file
+pub mod texture;
+pub mod render;
+pub mod integrator;
+pub mod environment;
+pub mod postprocess;
+pub mod scene;
+pub mod scenes;
+pub mod rng;
\ No newline at end of file
@@ -1,74 +1,322 @@
+use std::{path::Path, process::ExitCode, sync::Arc};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use ray_trace::{
-    camera::Camera,
-    hit::HittableList,
-    material::materials::{Dielectric, Lambertian, Metal, Mirror},
-    model::{rectangle::Rectangle, sphere::Sphere, triangle::Triangle},
-    vec3::Vec3,
+    environment::{Environment, ImageEnvironment, SkyGradient, SolidBackground},
+    integrator::{Albedo, AmbientOcclusion, DirectLighting, Normals},
+    postprocess::{PostProcess, ToneMapping, TransferFunction},
+    rng,
+    scene::{load_scene, Scene},
+    scenes::{builtin_scene, BUILTIN_SCENES},
+    stereo::{Stereo, StereoLayout},
+    writer::OutputFormat,
 };
 
-fn main() {
-    let mut world = HittableList::new();
-
-    let material_ground = Lambertian::new(Vec3::new(0.8, 0.8, 0.0));
-    let material_center = Lambertian::new(Vec3::new(0.1, 0.2, 0.5));
-    let material_left = Dielectric::new(1.5);
-    let material_right = Metal::new(Vec3::new(0.8, 0.6, 0.2), 0.0);
-
-    let l1 = Vec3::new(-1.0, 0.0, -1.0);
-    let l2 = Vec3::new(1.0, 0.0, -1.0);
-    let l3 = Vec3::new(0.0, 0.0, 1.0);
-    let l4 = Vec3::new(0.0, 1.0, 0.0);
-
-    world.add(Box::new(Triangle::new(
-        l1,
-        l2,
-        l3,
-        material_center.clone(),
-    )));
-    world.add(Box::new(Triangle::new(
-        l1,
-        l2,
-        l4,
-        material_center.clone(),
-    )));
-    world.add(Box::new(Triangle::new(
-        l1,
-        l3,
-        l4,
-        material_center.clone(),
-    )));
-    world.add(Box::new(Triangle::new(
-        l2,
-        l3,
-        l4,
-        material_center.clone(),
-    )));
-
-    let world = world;
-
-    let lookfrom = Vec3::new(-2.0, 2.0, 1.0);
-    let lookat = Vec3::new(0.0, 0.0, -1.0);
-    let vup = Vec3::new(0.0, 1.0, 0.0);
-
-    let defocus = (0.6, 3.4);
-
-    let mut camera = Camera::new(
-        (1920, 1080),
-        "output.png",
-        50,
-        100,
-        90.0,
-        (lookfrom, lookat, vup),
-        defocus,
-    );
+#[derive(Parser)]
+#[command(name = "raytrace", about = "Render a scene file or one of the built-in scenes")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Render a TOML scene file or a built-in scene
+    Render(Box<RenderArgs>),
+    /// List the built-in scenes
+    ListScenes,
+}
+
+#[derive(Args)]
+struct RenderArgs {
+    /// Path to a TOML scene file, or the name of a built-in scene
+    scene: String,
+    /// Image width in pixels, keeps the aspect ratio when given without --height
+    #[arg(long)]
+    width: Option<u32>,
+    /// Image height in pixels, keeps the aspect ratio when given without --width
+    #[arg(long)]
+    height: Option<u32>,
+    /// Samples per pixel
+    #[arg(short, long)]
+    samples: Option<u32>,
+    /// Maximum number of bounces per path
+    #[arg(long)]
+    max_depth: Option<u32>,
+    /// Let Russian roulette end paths after this many bounces
+    #[arg(long, value_name = "MIN_DEPTH")]
+    russian_roulette: Option<u32>,
+    /// Rendering algorithm, the others are for diagnosing scenes
+    #[arg(short, long, value_enum, default_value_t = IntegratorKind::Path)]
+    integrator: IntegratorKind,
+    /// How far ambient occlusion looks for occluders
+    #[arg(long, default_value_t = 1.0)]
+    ao_distance: f64,
+    /// Focus on what is seen at a point of the image, from 0 0 (top left) to 1 1,
+    /// the center when given no point
+    #[arg(long, num_args = 0..=2, value_names = ["S", "T"])]
+    autofocus: Option<Vec<f64>>,
+    /// Render a left and a right eye view into one image
+    #[arg(long, value_enum)]
+    stereo: Option<Layout>,
+    /// Distance between the eyes of a stereo pair, defaults to 0.064
+    #[arg(long)]
+    ipd: Option<f64>,
+    /// Distance at which the eye views line up, defaults to the focus distance
+    #[arg(long)]
+    convergence: Option<f64>,
+    /// Background seen by rays that miss the scene: sky, black, or the path of
+    /// an equirectangular image. Replaces the scene's environment
+    #[arg(long, value_name = "ENVIRONMENT")]
+    environment: Option<String>,
+    /// Exposure compensation in stops, for 8-bit output formats
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    exposure: f64,
+    /// How radiance above 1 is brought into range, for 8-bit output formats
+    #[arg(long, value_enum, default_value_t = ToneMap::Clamp)]
+    tone_map: ToneMap,
+    /// Encoding of 8-bit output: srgb, linear, or a gamma such as 2.2
+    #[arg(long, default_value = "srgb", value_parser = parse_transfer)]
+    transfer: TransferFunction,
+    /// Number of render threads, defaults to one per core
+    #[arg(short = 'j', long)]
+    threads: Option<usize>,
+    /// Output image path
+    #[arg(short, long)]
+    output: Option<String>,
+    /// Output format, replaces the extension of the output path
+    #[arg(short, long, value_enum)]
+    format: Option<Format>,
+    /// Seed for the random number generator, makes renders reproducible
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Hide the progress bar
+    #[arg(long)]
+    no_progress: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum IntegratorKind {
+    /// Path tracing, with light sampling when the scene has lights
+    Path,
+    /// Emission and light reaching the first surface in one bounce
+    Direct,
+    /// Surface normals as colors
+    Normals,
+    /// Surface colors without lighting
+    Albedo,
+    /// Ambient occlusion within --ao-distance
+    Ao,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ToneMap {
+    /// Clip at 1
+    Clamp,
+    /// x / (1 + x), keeps highlights from clipping
+    Reinhard,
+    /// Filmic curve fitted to ACES
+    Aces,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Layout {
+    SideBySide,
+    TopBottom,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Png,
+    Jpg,
+    Bmp,
+    Ppm,
+    Exr,
+    Pfm,
+}
+
+impl Format {
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Png => "png",
+            Format::Jpg => "jpg",
+            Format::Bmp => "bmp",
+            Format::Ppm => "ppm",
+            Format::Exr => "exr",
+            Format::Pfm => "pfm",
+        }
+    }
+}
+
+fn parse_transfer(value: &str) -> Result<TransferFunction, String> {
+    match value {
+        "srgb" => Ok(TransferFunction::Srgb),
+        "linear" => Ok(TransferFunction::Linear),
+        gamma => match gamma.parse::<f64>() {
+            Ok(gamma) if gamma > 0.0 && gamma.is_finite() => Ok(TransferFunction::Gamma(gamma)),
+            _ => Err("expected srgb, linear or a positive gamma".to_string()),
+        },
+    }
+}
+
+fn load(scene: &str) -> Result<Scene, String> {
+    if let Some(scene) = builtin_scene(scene) {
+        return Ok(scene);
+    }
+    if Path::new(scene).exists() {
+        return load_scene(scene).map_err(|err| format!("{}: {}", scene, err));
+    }
+    Err(format!(
+        "`{}` is neither a scene file nor a built-in scene, see `raytrace list-scenes`",
+        scene
+    ))
+}
+
+fn render(args: RenderArgs) -> Result<(), String> {
+    // seed before loading, built-in scenes may be randomly generated
+    if let Some(seed) = args.seed {
+        rng::seed(seed);
+    }
+    let Scene {
+        world,
+        lights,
+        camera: mut settings,
+        environment,
+    } = load(&args.scene)?;
+
+    let (width, height) = settings.image_size;
+    settings.image_size = match (args.width, args.height) {
+        (Some(w), Some(h)) => (w, h),
+        (Some(w), None) => (w, ((w as f64 * height as f64 / width as f64).round() as u32).max(1)),
+        (None, Some(h)) => (((h as f64 * width as f64 / height as f64).round() as u32).max(1), h),
+        (None, None) => (width, height),
+    };
+    if let Some(samples) = args.samples {
+        settings.samples_per_pixel = samples;
+    }
+    if let Some(max_depth) = args.max_depth {
+        settings.max_depth = max_depth;
+    }
+    if let Some(min_depth) = args.russian_roulette {
+        settings.russian_roulette = Some(min_depth);
+    }
+    match args.autofocus.as_deref() {
+        None => {}
+        Some([]) => settings.autofocus = Some((0.5, 0.5)),
+        Some([s, t]) => settings.autofocus = Some((*s, *t)),
+        Some(_) => return Err("--autofocus takes no point or both coordinates".to_string()),
+    }
+    // stereo flags override what the scene file set up
+    let layout = match args.stereo {
+        Some(Layout::SideBySide) => Some(StereoLayout::SideBySide),
+        Some(Layout::TopBottom) => Some(StereoLayout::TopBottom),
+        None => settings.stereo.map(|stereo| stereo.layout),
+    };
+    if let Some(layout) = layout {
+        let ipd = args
+            .ipd
+            .or(settings.stereo.map(|stereo| stereo.ipd))
+            .unwrap_or(0.064);
+        let convergence = args
+            .convergence
+            .or(settings.stereo.and_then(|stereo| stereo.convergence));
+        let stereo = Stereo::new(ipd, convergence, layout).map_err(|err| err.to_string())?;
+        settings.stereo = Some(stereo);
+    }
+    if let Some(output) = args.output {
+        settings.filepath = output;
+    }
+    if let Some(format) = args.format {
+        settings.filepath = Path::new(&settings.filepath)
+            .with_extension(format.extension())
+            .to_string_lossy()
+            .into_owned();
+    }
+
+    let mut camera = settings.build().map_err(|err| err.to_string())?;
+    if !lights.objects.is_empty() {
+        camera.set_lights(lights);
+    }
+    let environment: Option<Arc<dyn Environment>> = match args.environment.as_deref() {
+        None => environment,
+        Some("sky") => Some(SkyGradient::default_sky()),
+        Some("black") => Some(SolidBackground::black()),
+        Some(path) => Some(
+            ImageEnvironment::load(path)
+                .map_err(|err| format!("failed to load {}: {}", path, err))?,
+        ),
+    };
+    if let Some(environment) = environment {
+        camera.set_environment(environment);
+    }
+    match args.integrator {
+        IntegratorKind::Path => {}
+        IntegratorKind::Direct => camera.set_integrator(DirectLighting::new()),
+        IntegratorKind::Normals => camera.set_integrator(Normals::new()),
+        IntegratorKind::Albedo => camera.set_integrator(Albedo::new()),
+        IntegratorKind::Ao => {
+            if args.ao_distance <= 0.0 {
+                return Err("ambient occlusion distance must be positive".to_string());
+            }
+            camera.set_integrator(AmbientOcclusion::new(args.ao_distance));
+        }
+    }
+    if !args.exposure.is_finite() {
+        return Err("exposure must be finite".to_string());
+    }
+    let tone_mapping = match args.tone_map {
+        ToneMap::Clamp => ToneMapping::Clamp,
+        ToneMap::Reinhard => ToneMapping::Reinhard,
+        ToneMap::Aces => ToneMapping::AcesFilmic,
+    };
+    camera.set_post_process(PostProcess::new(args.exposure, tone_mapping, args.transfer));
+    if let Some(seed) = args.seed {
+        camera.set_seed(seed);
+    }
+    if args.no_progress {
+        camera.disable_progress_bar();
+    }
+
+    let multithread = args.threads != Some(1);
+    if let Some(threads) = args.threads.filter(|&threads| threads > 1) {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .map_err(|err| format!("failed to start {} render threads: {}", threads, err))?;
+    }
 
     let start_time = std::time::Instant::now();
-    camera.render(&world, true);
+    camera.render(&world, multithread);
     let end_time = std::time::Instant::now();
     println!(
         "Render time: {} seconds",
         (end_time - start_time).as_secs_f64()
     );
 
-    camera.output();
+    let filepath = camera.filepath.clone();
+    let format = OutputFormat::from_path(&filepath);
+    camera
+        .output_as(&filepath, format)
+        .map_err(|err| format!("failed to write {}: {}", filepath, err))
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Render(args) => render(*args),
+        Command::ListScenes => {
+            for scene in BUILTIN_SCENES {
+                println!("{:<16} {}", scene.name, scene.description);
+            }
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -1,43 +1,76 @@
-use std::sync::Arc;
+use std::{f64::consts::PI, sync::Arc};
 
-use rand::random;
+use crate::rng::random;
 
-use crate::{ray::Ray, hit::HitRecord, vec3::Vec3};
+use crate::{
+    hit::HitRecord,
+    ray::Ray,
+    texture::textures::{SolidColor, Texture},
+    vec3::Vec3,
+};
 
 pub trait Material: Send + Sync {
     fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Vec3, Ray)>;
+
+    // Density of the directions `scatter` samples, evaluated at `scattered`.
+    // The BSDF times cosine is then attenuation * scattering_pdf. Zero marks
+    // specular materials, which light sampling skips.
+    fn scattering_pdf(&self, ray_in: &Ray, hit_record: &HitRecord, scattered: &Ray) -> f64 {
+        let _ = (ray_in, hit_record, scattered);
+        0.0
+    }
+
+    // radiance emitted by the surface itself, black for everything but lights
+    fn emitted(&self, hit_record: &HitRecord) -> Vec3 {
+        let _ = hit_record;
+        Vec3::new_zero()
+    }
 }
 
 pub struct Lambertian {
-    pub albedo: Vec3,
+    pub albedo: Arc<dyn Texture>,
 }
 
 impl Lambertian {
     pub fn new(albedo: Vec3) -> Arc<Self> {
+        Lambertian::new_texture(SolidColor::new(albedo))
+    }
+
+    pub fn new_texture(albedo: Arc<dyn Texture>) -> Arc<Self> {
         Arc::new(Lambertian { albedo })
     }
 }
 
 impl Material for Lambertian {
     fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Vec3, Ray)> {
-        let _ = ray_in;
         let mut scatter_direction = hit_record.normal + Vec3::random_unit_vector();
         if scatter_direction.near_zero() {
             scatter_direction = hit_record.normal;
         }
-        let scattered = Ray::new(hit_record.p, scatter_direction);
-        let attenuation = self.albedo;
+        let scattered = Ray::new_with_time(hit_record.p, scatter_direction, ray_in.time);
+        let attenuation = self.albedo.value(hit_record.u, hit_record.v, &hit_record.p);
         Some((attenuation, scattered))
-    }   
+    }
+
+    // `scatter` is cosine weighted
+    fn scattering_pdf(&self, ray_in: &Ray, hit_record: &HitRecord, scattered: &Ray) -> f64 {
+        let _ = ray_in;
+        let cosine = hit_record.normal.dot(&scattered.direction.unit());
+        if cosine < 0.0 { 0.0 } else { cosine / PI }
+    }
 }
 
 pub struct Metal {
-    pub albedo: Vec3,
+    pub albedo: Arc<dyn Texture>,
     pub fuzz: f64
 }
 
 impl Metal {
     pub fn new(albedo: Vec3, fuzz: f64) -> Arc<Self> {
+        Metal::new_texture(SolidColor::new(albedo), fuzz)
+    }
+
+    pub fn new_texture(albedo: Arc<dyn Texture>, fuzz: f64) -> Arc<Self> {
         Arc::new(Metal { 
             albedo,  
             fuzz: if fuzz < 1.0 { fuzz } else { 1.0 }
@@ -48,8 +81,12 @@ impl Metal {
 impl Material for Metal {
     fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Vec3, Ray)> {
         let reflected = ray_in.direction.unit().reflect(&hit_record.normal);
-        let scattered = Ray::new(hit_record.p, reflected + Vec3::random_in_unit_sphere() * self.fuzz);
-        let attenuation = self.albedo;
+        let scattered = Ray::new_with_time(
+            hit_record.p,
+            reflected + Vec3::random_in_unit_sphere() * self.fuzz,
+            ray_in.time,
+        );
+        let attenuation = self.albedo.value(hit_record.u, hit_record.v, &hit_record.p);
         if scattered.direction.dot(&hit_record.normal) > 0.0 {
             return Some((attenuation, scattered))
         }
@@ -88,7 +125,7 @@ impl Material for Dielectric {
             unit_direction.refract(&hit_record.normal, refraction_ratio)
         };
         
-        let scattered = Ray::new(hit_record.p, direction);
+        let scattered = Ray::new_with_time(hit_record.p, direction, ray_in.time);
         Some((attenuation, scattered))
     }
 }
@@ -104,11 +141,62 @@ impl Mirror {
 impl Material for Mirror {
     fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Vec3, Ray)> {
         let reflected = ray_in.direction.unit().reflect(&hit_record.normal);
-        let scattered = Ray::new(hit_record.p, reflected);
+        let scattered = Ray::new_with_time(hit_record.p, reflected, ray_in.time);
         let attenuation = Vec3::new(1.0, 1.0, 1.0);
         if scattered.direction.dot(&hit_record.normal) > 0.0 {
             return Some((attenuation, scattered))
         }
         None
     }
+}
+
+pub struct DiffuseLight {
+    pub emit: Vec3,
+}
+
+impl DiffuseLight {
+    pub fn new(emit: Vec3) -> Arc<Self> {
+        Arc::new(DiffuseLight { emit })
+    }
+}
+
+impl Material for DiffuseLight {
+    fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Vec3, Ray)> {
+        let _ = (ray_in, hit_record);
+        None
+    }
+
+    fn emitted(&self, hit_record: &HitRecord) -> Vec3 {
+        let _ = hit_record;
+        self.emit
+    }
+}
+
+/// Phase function of a participating medium, scatters uniformly in all
+/// directions.
+pub struct Isotropic {
+    pub albedo: Arc<dyn Texture>,
+}
+
+impl Isotropic {
+    pub fn new(albedo: Vec3) -> Arc<Self> {
+        Isotropic::new_texture(SolidColor::new(albedo))
+    }
+
+    pub fn new_texture(albedo: Arc<dyn Texture>) -> Arc<Self> {
+        Arc::new(Isotropic { albedo })
+    }
+}
+
+impl Material for Isotropic {
+    fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Vec3, Ray)> {
+        let scattered = Ray::new_with_time(hit_record.p, Vec3::random_unit_vector(), ray_in.time);
+        let attenuation = self.albedo.value(hit_record.u, hit_record.v, &hit_record.p);
+        Some((attenuation, scattered))
+    }
+
+    fn scattering_pdf(&self, ray_in: &Ray, hit_record: &HitRecord, scattered: &Ray) -> f64 {
+        let _ = (ray_in, hit_record, scattered);
+        1.0 / (4.0 * PI)
+    }
 }
\ No newline at end of file
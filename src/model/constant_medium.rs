@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use crate::aabb::Aabb;
+use crate::hit::{HitRecord, Hittable};
+use crate::interval::{Interval, UNIVERSE};
+use crate::material::materials::{Isotropic, Material};
+use crate::ray::Ray;
+use crate::rng::random;
+use crate::texture::textures::Texture;
+use crate::vec3::Vec3;
+
+/// Fog or smoke of uniform density filling a closed `boundary`.
+///
+/// A ray entering the volume travels a random, exponentially distributed
+/// distance before it scatters; denser media scatter sooner. If that
+/// distance is longer than the way through the volume the ray passes
+/// unaffected. The boundary must be convex.
+pub struct ConstantMedium {
+    pub boundary: Arc<dyn Hittable>,
+    pub phase_function: Arc<dyn Material>,
+    neg_inv_density: f64,
+}
+
+impl ConstantMedium {
+    /// `albedo` is the fraction of light kept per scattering event, per
+    /// channel, so a colored albedo gives colored absorption.
+    pub fn new(boundary: Arc<dyn Hittable>, density: f64, albedo: Vec3) -> Self {
+        ConstantMedium::new_phase(boundary, density, Isotropic::new(albedo))
+    }
+
+    pub fn new_texture(
+        boundary: Arc<dyn Hittable>,
+        density: f64,
+        albedo: Arc<dyn Texture>,
+    ) -> Self {
+        ConstantMedium::new_phase(boundary, density, Isotropic::new_texture(albedo))
+    }
+
+    /// Panics unless `density` is positive, the scene loader reports such
+    /// values as errors before it gets here.
+    pub fn new_phase(
+        boundary: Arc<dyn Hittable>,
+        density: f64,
+        phase_function: Arc<dyn Material>,
+    ) -> Self {
+        assert!(density > 0.0, "medium density must be positive");
+        ConstantMedium {
+            boundary,
+            phase_function,
+            neg_inv_density: -1.0 / density,
+        }
+    }
+}
+
+impl Hittable for ConstantMedium {
+    fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord> {
+        // where the ray line enters and leaves the boundary, the origin may
+        // already be inside
+        let enter = self.boundary.hit(ray, &UNIVERSE)?;
+        let exit = self
+            .boundary
+            .hit(ray, &Interval::new(enter.t + 0.0001, f64::INFINITY))?;
+
+        let t_enter = enter.t.max(interval.min).max(0.0);
+        let t_exit = exit.t.min(interval.max);
+        if t_enter >= t_exit {
+            return None;
+        }
+
+        let ray_length = ray.direction.length();
+        let distance_inside = (t_exit - t_enter) * ray_length;
+        let hit_distance = self.neg_inv_density * random::<f64>().ln();
+        if hit_distance > distance_inside {
+            return None;
+        }
+
+        let t = t_enter + hit_distance / ray_length;
+        let mut result = HitRecord::new(
+            ray.at(t),
+            // scattering inside a volume has no surface, any normal will do
+            Vec3::new(1.0, 0.0, 0.0),
+            t,
+            Arc::clone(&self.phase_function),
+        );
+        result.front_face = true;
+        Some(result)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.boundary.bounding_box()
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use crate::aabb::Aabb;
+use crate::hit::{HitRecord, Hittable};
+use crate::interval::Interval;
+use crate::material::materials::Material;
+use crate::model::quad::Quad;
+use crate::ray::Ray;
+use crate::vec3::Vec3;
+
+/// Axis aligned box made of six quads, all facing outwards.
+pub struct Cuboid {
+    pub sides: Vec<Quad>,
+    bbox: Aabb,
+}
+
+impl Cuboid {
+    /// `a` and `b` are any two opposite corners.
+    pub fn new(a: Vec3, b: Vec3, material: Arc<dyn Material>) -> Self {
+        let min = Vec3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z));
+        let max = Vec3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z));
+        let dx = Vec3::new(max.x - min.x, 0.0, 0.0);
+        let dy = Vec3::new(0.0, max.y - min.y, 0.0);
+        let dz = Vec3::new(0.0, 0.0, max.z - min.z);
+
+        let sides = vec![
+            // front, right, back, left
+            Quad::new(
+                Vec3::new(min.x, min.y, max.z),
+                dx,
+                dy,
+                Arc::clone(&material),
+            ),
+            Quad::new(
+                Vec3::new(max.x, min.y, max.z),
+                dz * -1.0,
+                dy,
+                Arc::clone(&material),
+            ),
+            Quad::new(
+                Vec3::new(max.x, min.y, min.z),
+                dx * -1.0,
+                dy,
+                Arc::clone(&material),
+            ),
+            Quad::new(
+                Vec3::new(min.x, min.y, min.z),
+                dz,
+                dy,
+                Arc::clone(&material),
+            ),
+            // top, bottom
+            Quad::new(
+                Vec3::new(min.x, max.y, max.z),
+                dx,
+                dz * -1.0,
+                Arc::clone(&material),
+            ),
+            Quad::new(Vec3::new(min.x, min.y, min.z), dx, dz, material),
+        ];
+        Cuboid {
+            sides,
+            bbox: Aabb::new_from_points(min, max),
+        }
+    }
+}
+
+impl Hittable for Cuboid {
+    fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord> {
+        let mut hit_record = None;
+        let mut closest_so_far = interval.max;
+        for side in self.sides.iter() {
+            if let Some(record) = side.hit(ray, &Interval::new(interval.min, closest_so_far)) {
+                closest_so_far = record.t;
+                hit_record = Some(record);
+            }
+        }
+        hit_record
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+}
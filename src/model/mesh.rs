@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use crate::aabb::Aabb;
+use crate::bvh::BvhNode;
+use crate::hit::{HitRecord, Hittable, HittableList};
+use crate::interval::Interval;
+use crate::material::materials::Material;
+use crate::ray::Ray;
+use crate::vec3::Vec3;
+
+/// One triangle of a mesh, as indices into the shared vertex arrays.
+#[derive(Debug, Clone)]
+pub struct MeshFace {
+    pub positions: [usize; 3],
+    pub normals: Option<[usize; 3]>,
+    pub uvs: Option<[usize; 3]>,
+    pub material: usize,
+}
+
+/// Vertex data shared by all triangles of a `TriangleMesh`.
+pub struct MeshData {
+    pub positions: Vec<Vec3>,
+    pub normals: Vec<Vec3>,
+    pub uvs: Vec<(f64, f64)>,
+    pub faces: Vec<MeshFace>,
+    pub materials: Vec<Arc<dyn Material>>,
+}
+
+/// Indexed triangle mesh. Vertices are stored once and referenced by every
+/// face using them; faces are organized in a BVH.
+pub struct TriangleMesh {
+    pub data: Arc<MeshData>,
+    bvh: BvhNode,
+}
+
+struct MeshTriangle {
+    mesh: Arc<MeshData>,
+    face: usize,
+}
+
+impl TriangleMesh {
+    pub fn new(data: MeshData) -> Self {
+        let data = Arc::new(data);
+        let mut triangles = HittableList::new();
+        for face in 0..data.faces.len() {
+            triangles.add(Box::new(MeshTriangle {
+                mesh: Arc::clone(&data),
+                face,
+            }));
+        }
+        TriangleMesh {
+            data,
+            bvh: BvhNode::new(triangles),
+        }
+    }
+}
+
+impl Hittable for TriangleMesh {
+    fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord> {
+        self.bvh.hit(ray, interval)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bvh.bounding_box()
+    }
+}
+
+impl MeshTriangle {
+    fn vertices(&self) -> (Vec3, Vec3, Vec3) {
+        let [a, b, c] = self.mesh.faces[self.face].positions;
+        let positions = &self.mesh.positions;
+        (positions[a], positions[b], positions[c])
+    }
+}
+
+impl Hittable for MeshTriangle {
+    fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord> {
+        // Moller-Trumbore Algorithm, same as `Triangle`
+        let face = &self.mesh.faces[self.face];
+        let (a, b, c) = self.vertices();
+        let edge1 = b - a;
+        let edge2 = c - a;
+        let h = ray.direction.cross(&edge2);
+        let det = edge1.dot(&h);
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        let f = 1.0 / det;
+        let s = ray.origin - a;
+        let u = f * s.dot(&h);
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(&edge1);
+        let v = f * ray.direction.dot(&q);
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = f * edge2.dot(&q);
+        if !interval.contains(t) {
+            return None;
+        }
+
+        let geometric_normal = edge1.cross(&edge2).unit();
+        let w = 1.0 - u - v;
+        let shading_normal = match face.normals {
+            Some([na, nb, nc]) => {
+                let normals = &self.mesh.normals;
+                (normals[na] * w + normals[nb] * u + normals[nc] * v).unit()
+            }
+            None => geometric_normal,
+        };
+
+        let mut result = HitRecord::new(
+            ray.at(t),
+            shading_normal,
+            t,
+            Arc::clone(&self.mesh.materials[face.material]),
+        );
+        // the side is decided by the real surface, the interpolated normal only shades
+        result.front_face = ray.direction.dot(&geometric_normal) < 0.0;
+        if shading_normal.dot(&geometric_normal) < 0.0 {
+            result.normal *= -1.0;
+        }
+        if !result.front_face {
+            result.normal *= -1.0;
+        }
+        (result.u, result.v) = match face.uvs {
+            Some([ta, tb, tc]) => {
+                let uvs = &self.mesh.uvs;
+                (
+                    uvs[ta].0 * w + uvs[tb].0 * u + uvs[tc].0 * v,
+                    uvs[ta].1 * w + uvs[tb].1 * u + uvs[tc].1 * v,
+                )
+            }
+            None => (u, v),
+        };
+        Some(result)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        let (a, b, c) = self.vertices();
+        Aabb::new_enclosing(&Aabb::new_from_points(a, b), &Aabb::new_from_points(a, c))
+    }
+}
@@ -1,3 +1,9 @@
+pub mod constant_medium;
+pub mod cuboid;
+pub mod mesh;
+pub mod obj;
+pub mod quad;
 pub mod rectangle;
 pub mod sphere;
+pub mod transformed;
 pub mod triangle;
\ No newline at end of file
@@ -0,0 +1,313 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use image::ImageError;
+
+use crate::material::materials::{Dielectric, DiffuseLight, Lambertian, Material, Metal};
+use crate::model::mesh::{MeshData, MeshFace, TriangleMesh};
+use crate::texture::textures::ImageTexture;
+use crate::vec3::Vec3;
+
+#[derive(Debug)]
+pub struct ObjError {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Display for ObjError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path.display(), line, self.message),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+impl std::error::Error for ObjError {}
+
+/// Material description read from an MTL file, before it is mapped onto
+/// one of the renderer's materials.
+#[derive(Debug, Clone)]
+pub struct MtlMaterial {
+    pub name: String,
+    pub diffuse: Vec3,
+    pub specular: Vec3,
+    pub emission: Vec3,
+    pub shininess: f64,
+    pub ior: f64,
+    pub dissolve: f64,
+    pub illum: u32,
+    pub diffuse_map: Option<PathBuf>,
+}
+
+impl MtlMaterial {
+    fn new(name: &str) -> Self {
+        MtlMaterial {
+            name: name.to_string(),
+            diffuse: Vec3::new(0.8, 0.8, 0.8),
+            specular: Vec3::new_zero(),
+            emission: Vec3::new_zero(),
+            shininess: 0.0,
+            ior: 1.5,
+            dissolve: 1.0,
+            illum: 2,
+            diffuse_map: None,
+        }
+    }
+
+    /// Picks the closest material the renderer has:
+    /// emissive surfaces become lights, transparent ones glass, mirror-like
+    /// ones metal (Phong exponent mapped to fuzz) and everything else diffuse.
+    /// A `map_Kd` image replaces the diffuse color.
+    pub fn to_material(&self) -> Result<Arc<dyn Material>, ImageError> {
+        let max = |v: &Vec3| v.x.max(v.y).max(v.z);
+        if max(&self.emission) > 0.0 {
+            return Ok(DiffuseLight::new(self.emission));
+        }
+        if self.dissolve < 1.0 || matches!(self.illum, 4 | 6 | 7 | 9) {
+            return Ok(Dielectric::new(self.ior));
+        }
+        if self.illum == 3 || (max(&self.specular) > 0.0 && max(&self.diffuse) < 0.01) {
+            let fuzz = (2.0 / (self.shininess + 2.0)).sqrt();
+            return Ok(Metal::new(self.specular, fuzz));
+        }
+        match &self.diffuse_map {
+            Some(path) => Ok(Lambertian::new_texture(ImageTexture::load(path)?)),
+            None => Ok(Lambertian::new(self.diffuse)),
+        }
+    }
+}
+
+struct Parser {
+    path: PathBuf,
+    line: usize,
+}
+
+impl Parser {
+    fn error(&self, message: String) -> ObjError {
+        ObjError {
+            path: self.path.clone(),
+            line: Some(self.line),
+            message,
+        }
+    }
+
+    fn floats<const N: usize>(&self, args: &[&str], keyword: &str) -> Result<[f64; N], ObjError> {
+        let mut values = [0.0; N];
+        if args.len() < N {
+            return Err(self.error(format!("`{}` expects {} numbers", keyword, N)));
+        }
+        for (value, arg) in values.iter_mut().zip(args) {
+            *value = arg
+                .parse()
+                .map_err(|_| self.error(format!("invalid number `{}` in `{}`", arg, keyword)))?;
+        }
+        Ok(values)
+    }
+
+    // OBJ indices are 1-based, negative values count back from the last element
+    fn index(&self, token: &str, count: usize, kind: &str) -> Result<usize, ObjError> {
+        let index: i64 = token
+            .parse()
+            .map_err(|_| self.error(format!("invalid {} index `{}`", kind, token)))?;
+        let resolved = if index > 0 {
+            index - 1
+        } else {
+            count as i64 + index
+        };
+        if index == 0 || resolved < 0 || resolved >= count as i64 {
+            return Err(self.error(format!("{} index {} out of range", kind, index)));
+        }
+        Ok(resolved as usize)
+    }
+}
+
+fn read(path: &Path) -> Result<String, ObjError> {
+    std::fs::read_to_string(path).map_err(|err| ObjError {
+        path: path.to_path_buf(),
+        line: None,
+        message: err.to_string(),
+    })
+}
+
+pub fn load_mtl<P: AsRef<Path>>(path: P) -> Result<Vec<MtlMaterial>, ObjError> {
+    let path = path.as_ref();
+    let source = read(path)?;
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    let mut parser = Parser {
+        path: path.to_path_buf(),
+        line: 0,
+    };
+
+    let mut materials: Vec<MtlMaterial> = Vec::new();
+    for (number, line) in source.lines().enumerate() {
+        parser.line = number + 1;
+        let mut tokens = line.split_whitespace();
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+        let args: Vec<&str> = tokens.collect();
+        if keyword.starts_with('#') {
+            continue;
+        }
+        if keyword == "newmtl" {
+            let name = args.join(" ");
+            materials.push(MtlMaterial::new(&name));
+            continue;
+        }
+        let Some(material) = materials.last_mut() else {
+            return Err(parser.error(format!("`{}` before any `newmtl`", keyword)));
+        };
+        match keyword {
+            "Kd" | "Ks" | "Ke" => {
+                let [r, g, b] = parser.floats::<3>(&args, keyword)?;
+                let color = Vec3::new(r, g, b);
+                match keyword {
+                    "Kd" => material.diffuse = color,
+                    "Ks" => material.specular = color,
+                    _ => material.emission = color,
+                }
+            }
+            "Ns" => material.shininess = parser.floats::<1>(&args, keyword)?[0],
+            "Ni" => material.ior = parser.floats::<1>(&args, keyword)?[0],
+            "d" => material.dissolve = parser.floats::<1>(&args, keyword)?[0],
+            "Tr" => material.dissolve = 1.0 - parser.floats::<1>(&args, keyword)?[0],
+            "illum" => {
+                material.illum = args
+                    .first()
+                    .and_then(|arg| arg.parse().ok())
+                    .ok_or_else(|| parser.error("`illum` expects an integer".to_string()))?
+            }
+            // the file name is the last argument, map options may precede it
+            "map_Kd" => material.diffuse_map = args.last().map(|file| base_dir.join(file)),
+            // other statements (Ka, map_Bump, ...) have no equivalent here
+            _ => {}
+        }
+    }
+    Ok(materials)
+}
+
+/// Loads a Wavefront OBJ file into a single `TriangleMesh`.
+///
+/// Polygons are fan triangulated. Materials come from the `mtllib` files the
+/// OBJ references; faces without a `usemtl` use `default_material`.
+pub fn load_obj<P: AsRef<Path>>(
+    path: P,
+    default_material: Arc<dyn Material>,
+) -> Result<TriangleMesh, ObjError> {
+    let path = path.as_ref();
+    let source = read(path)?;
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    let mut parser = Parser {
+        path: path.to_path_buf(),
+        line: 0,
+    };
+
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut faces = Vec::new();
+    let mut materials: Vec<Arc<dyn Material>> = vec![default_material];
+    let mut library: HashMap<String, MtlMaterial> = HashMap::new();
+    let mut material_ids: HashMap<String, usize> = HashMap::new();
+    let mut current_material = 0;
+
+    for (number, line) in source.lines().enumerate() {
+        parser.line = number + 1;
+        let mut tokens = line.split_whitespace();
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+        let args: Vec<&str> = tokens.collect();
+        match keyword {
+            "v" => {
+                let [x, y, z] = parser.floats::<3>(&args, keyword)?;
+                positions.push(Vec3::new(x, y, z));
+            }
+            "vn" => {
+                let [x, y, z] = parser.floats::<3>(&args, keyword)?;
+                normals.push(Vec3::new(x, y, z).unit());
+            }
+            "vt" => {
+                let [u] = parser.floats::<1>(&args, keyword)?;
+                let v = match args.get(1) {
+                    Some(_) => parser.floats::<2>(&args, keyword)?[1],
+                    None => 0.0,
+                };
+                uvs.push((u, v));
+            }
+            "f" => {
+                if args.len() < 3 {
+                    return Err(parser.error("a face needs at least 3 vertices".to_string()));
+                }
+                let mut corners = Vec::with_capacity(args.len());
+                for arg in args.iter() {
+                    // v, v/vt, v//vn or v/vt/vn
+                    let mut parts = arg.split('/');
+                    let position =
+                        parser.index(parts.next().unwrap_or(""), positions.len(), "vertex")?;
+                    let uv = match parts.next() {
+                        Some("") | None => None,
+                        Some(token) => {
+                            Some(parser.index(token, uvs.len(), "texture coordinate")?)
+                        }
+                    };
+                    let normal = match parts.next() {
+                        Some("") | None => None,
+                        Some(token) => Some(parser.index(token, normals.len(), "normal")?),
+                    };
+                    corners.push((position, uv, normal));
+                }
+
+                for i in 1..corners.len() - 1 {
+                    let (a, b, c) = (corners[0], corners[i], corners[i + 1]);
+                    faces.push(MeshFace {
+                        positions: [a.0, b.0, c.0],
+                        uvs: a.1.zip(b.1).zip(c.1).map(|((a, b), c)| [a, b, c]),
+                        normals: a.2.zip(b.2).zip(c.2).map(|((a, b), c)| [a, b, c]),
+                        material: current_material,
+                    });
+                }
+            }
+            "mtllib" => {
+                for file in args.iter() {
+                    for material in load_mtl(base_dir.join(file))? {
+                        library.insert(material.name.clone(), material);
+                    }
+                }
+            }
+            "usemtl" => {
+                let name = args.join(" ");
+                current_material = match material_ids.get(&name) {
+                    Some(&id) => id,
+                    None => {
+                        let material = library
+                            .get(&name)
+                            .ok_or_else(|| parser.error(format!("unknown material `{}`", name)))?;
+                        let material = material.to_material().map_err(|err| {
+                            parser.error(format!("material `{}`: {}", name, err))
+                        })?;
+                        materials.push(material);
+                        material_ids.insert(name, materials.len() - 1);
+                        materials.len() - 1
+                    }
+                };
+            }
+            // comments, groups, smoothing groups, lines and points are ignored
+            _ => {}
+        }
+    }
+
+    Ok(TriangleMesh::new(MeshData {
+        positions,
+        normals,
+        uvs,
+        faces,
+        materials,
+    }))
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use crate::aabb::Aabb;
+use crate::hit::{HitRecord, Hittable};
+use crate::interval::Interval;
+use crate::material::materials::Material;
+use crate::ray::Ray;
+use crate::rng::random;
+use crate::vec3::Vec3;
+
+/// Parallelogram spanned by the edges `u` and `v` from the corner `origin`.
+/// The front face is the side `u x v` points to.
+pub struct Quad {
+    pub origin: Vec3,
+    pub u: Vec3,
+    pub v: Vec3,
+    pub material: Arc<dyn Material>,
+    pub normal: Vec3,
+
+    // plane equation normal . p = d
+    d: f64,
+    // maps a point in the plane to its (alpha, beta) coordinates along u and v
+    w: Vec3,
+    bbox: Aabb,
+}
+
+impl Quad {
+    pub fn new(origin: Vec3, u: Vec3, v: Vec3, material: Arc<dyn Material>) -> Self {
+        let n = u.cross(&v);
+        let normal = n.unit();
+        let d = normal.dot(&origin);
+        let w = n / n.dot(&n);
+        let bbox = Aabb::new_enclosing(
+            &Aabb::new_from_points(origin, origin + u + v),
+            &Aabb::new_from_points(origin + u, origin + v),
+        );
+        Quad {
+            origin,
+            u,
+            v,
+            material,
+            normal,
+            d,
+            w,
+            bbox,
+        }
+    }
+}
+
+impl Hittable for Quad {
+    fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord> {
+        let denom = self.normal.dot(&ray.direction);
+        // parallel to the plane, relative to the direction so that short
+        // directions (e.g. from transformed rays) still hit
+        if denom.abs() < 1e-12 * ray.direction.length() {
+            return None;
+        }
+        let t = (self.d - self.normal.dot(&ray.origin)) / denom;
+        if !interval.contains(t) {
+            return None;
+        }
+
+        let p = ray.at(t);
+        let planar = p - self.origin;
+        let alpha = self.w.dot(&planar.cross(&self.v));
+        let beta = self.w.dot(&self.u.cross(&planar));
+        if !(0.0..=1.0).contains(&alpha) || !(0.0..=1.0).contains(&beta) {
+            return None;
+        }
+
+        let mut result = HitRecord::new(p, self.normal, t, Arc::clone(&self.material));
+        result.set_face_normal(ray, &self.normal);
+        (result.u, result.v) = (alpha, beta);
+        Some(result)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    // uniform over the quad's area, converted to solid angle
+    fn pdf_value(&self, origin: &Vec3, direction: &Vec3) -> f64 {
+        let Some(hit) = self.hit(
+            &Ray::new(*origin, *direction),
+            &Interval::new(0.001, f64::INFINITY),
+        ) else {
+            return 0.0;
+        };
+        let area = self.u.cross(&self.v).length();
+        let distance_squared = hit.t * hit.t * direction.length_squared();
+        let cosine = (direction.unit().dot(&self.normal)).abs();
+        distance_squared / (cosine * area)
+    }
+
+    fn random_direction(&self, origin: &Vec3) -> Vec3 {
+        let point = self.origin + self.u * random::<f64>() + self.v * random::<f64>();
+        point - *origin
+    }
+}
@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
+use crate::aabb::Aabb;
 use crate::hit::{HitRecord, Hittable};
-use crate::interval::Interval;
+use crate::interval::{Interval, UNIVERSE};
 use crate::material::materials::Material;
 use crate::ray::Ray;
+use crate::rng::random;
 use crate::vec3::Vec3;
 
 pub struct Rectangle {
@@ -12,69 +14,189 @@ pub struct Rectangle {
     pub left_up: Vec3,
     pub material: Arc<dyn Material>,
 
-    a: f64,
-    b: f64,
-    c: f64,
+    // plane equation normal . p = d, the plane passes through the center
     d: f64,
+    // `None` when the surface is unbounded and cannot be sampled as a light
+    surface: Option<Parallelogram>,
 }
 
+// corner and edges of a bounded surface, which is sampled as `origin + u * e1 + v * e2`
+struct Parallelogram {
+    origin: Vec3,
+    e1: Vec3,
+    e2: Vec3,
+    area: f64,
+}
+
+// tolerance for points on the border, relative to the rectangle size
+const EDGE_EPSILON: f64 = 1e-9;
+
 impl Rectangle {
+    /// Rectangle in the plane through the middle of `left_up` and
+    /// `right_down` with the given `normal`. Hits are limited to the box the
+    /// two corners span, on the axes along which they differ. When they
+    /// differ on all three, the axis most aligned with the normal is left to
+    /// the plane, so the surface is always a parallelogram.
     pub fn new(left_up: Vec3, right_down: Vec3, normal: Vec3, material: Arc<dyn Material>) -> Self {
-        // get surface equation
         let center = (left_up + right_down) / 2.0;
-        let (a_, b_, c_) = (normal.x, normal.y, normal.z);
-        let d_ = -a_ * center.x - b_ * center.y - c_ * center.z;
-        let (a, b, c, d) = (a_, b_, c_, d_);
-
-        Rectangle {
-            normal: normal.unit(),
+        let normal = normal.unit();
+        let mut rectangle = Rectangle {
+            normal,
             base: left_up - right_down,
             left_up,
             material,
-            a,
-            b,
-            c,
-            d,
+            d: normal.dot(&center),
+            surface: None,
+        };
+        rectangle.surface = rectangle.parallelogram();
+        rectangle
+    }
+
+    fn bounded(&self, axis: isize) -> bool {
+        self.base[axis] != 0.0
+    }
+
+    // The two axes the texture is laid out along. When the corners differ on
+    // all three axes, the one most aligned with the normal follows from the
+    // other two and is dropped.
+    fn uv_axes(&self) -> (isize, isize) {
+        let mut axes: Vec<isize> = (0..3).filter(|&axis| self.bounded(axis)).collect();
+        if axes.len() == 3 {
+            let dominant = (0..3)
+                .max_by(|&a, &b| self.normal[a].abs().total_cmp(&self.normal[b].abs()))
+                .unwrap_or(2);
+            axes.retain(|&axis| axis != dominant);
+        }
+        for axis in 0..3 {
+            if axes.len() >= 2 {
+                break;
+            }
+            if !axes.contains(&axis) {
+                axes.push(axis);
+            }
+        }
+        (axes[0], axes[1])
+    }
+
+    // the surface over the corners' range on the two uv axes, the third
+    // coordinate follows from the plane equation
+    fn parallelogram(&self) -> Option<Parallelogram> {
+        let (a_axis, b_axis) = self.uv_axes();
+        if !(self.bounded(a_axis) && self.bounded(b_axis)) {
+            return None;
+        }
+        let free = 3 - a_axis - b_axis;
+        if self.normal[free].abs() < 1e-12 {
+            return None;
         }
+
+        let right_down = self.left_up - self.base;
+        let corner = |a: f64, b: f64| {
+            let mut point = Vec3::new_zero();
+            point[a_axis] = a;
+            point[b_axis] = b;
+            point[free] =
+                (self.d - self.normal[a_axis] * a - self.normal[b_axis] * b) / self.normal[free];
+            point
+        };
+        let origin = corner(self.left_up[a_axis], self.left_up[b_axis]);
+        let e1 = corner(right_down[a_axis], self.left_up[b_axis]) - origin;
+        let e2 = corner(self.left_up[a_axis], right_down[b_axis]) - origin;
+        let area = e1.cross(&e2).length();
+        if area <= 0.0 || !area.is_finite() {
+            return None;
+        }
+        Some(Parallelogram {
+            origin,
+            e1,
+            e2,
+            area,
+        })
     }
 }
 
 impl Hittable for Rectangle {
     fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord> {
-        let t = -(self.a * ray.origin.x + self.b * ray.origin.y + self.c * ray.origin.z - self.d)
-            / (self.a * ray.direction.x + self.b * ray.direction.y + self.c * ray.direction.z);
-        let (x, y, z) = (
-            ray.origin.x + ray.direction.x * t,
-            ray.origin.y + ray.direction.y * t,
-            ray.origin.z + ray.direction.z * t,
-        );
-        // check if the point is in the rectangle
-        let root = Vec3::new(x, y, z);
-        let norm_coor = (self.left_up - root) / self.base;
-        if norm_coor.x < 0.0
-            || norm_coor.x > 1.0
-            || norm_coor.y < 0.0
-            || norm_coor.y > 1.0
-            || norm_coor.z < 0.0
-            || norm_coor.z > 1.0
-        {
+        let denom = self.normal.dot(&ray.direction);
+        // parallel rays never cross the plane, also avoids dividing by zero
+        if denom.abs() < 1e-12 * ray.direction.length() {
             return None;
         }
-
-        if !interval.contains((root - ray.origin).length()) {
+        // `t` is the ray parameter, like every other primitive
+        let t = (self.d - self.normal.dot(&ray.origin)) / denom;
+        if !interval.contains(t) {
             return None;
         }
 
-        let mut result = Box::new(HitRecord::new(
-            root,
-            self.normal,
-            (root - ray.origin).length(),
-            Arc::clone(&self.material),
-        ));
+        // check if the point is in the rectangle, `norm_coor` runs from 0 at
+        // `left_up` to 1 at the opposite corner
+        let root = ray.at(t);
+        let norm_coor = (self.left_up - root) / self.base;
+        let (u_axis, v_axis) = self.uv_axes();
+        for axis in [u_axis, v_axis] {
+            if self.bounded(axis)
+                && !(-EDGE_EPSILON..=1.0 + EDGE_EPSILON).contains(&norm_coor[axis])
+            {
+                return None;
+            }
+        }
 
+        let mut result = HitRecord::new(root, self.normal, t, Arc::clone(&self.material));
         let outward_normal = self.normal;
         result.set_face_normal(ray, &outward_normal);
+        let ratio = |axis: isize| {
+            if self.bounded(axis) {
+                norm_coor[axis].clamp(0.0, 1.0)
+            } else {
+                0.0
+            }
+        };
+        // left_up is the top left corner of the texture
+        (result.u, result.v) = (ratio(u_axis), 1.0 - ratio(v_axis));
+
+        Some(result)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        if let Some(surface) = &self.surface {
+            let (origin, e1, e2) = (surface.origin, surface.e1, surface.e2);
+            return Aabb::new_enclosing(
+                &Aabb::new_from_points(origin, origin + e1 + e2),
+                &Aabb::new_from_points(origin + e1, origin + e2),
+            );
+        }
+        let right_down = self.left_up - self.base;
+        let corners = Aabb::new_from_points(self.left_up, right_down);
+        let mut intervals = [corners.x, corners.y, corners.z];
+        for (axis, interval) in intervals.iter_mut().enumerate() {
+            if !self.bounded(axis as isize) {
+                *interval = UNIVERSE;
+            }
+        }
+        Aabb::new(intervals[0], intervals[1], intervals[2])
+    }
+
+    // uniform over the surface, converted to solid angle
+    fn pdf_value(&self, origin: &Vec3, direction: &Vec3) -> f64 {
+        let Some(surface) = &self.surface else {
+            return 0.0;
+        };
+        let Some(hit) = self.hit(
+            &Ray::new(*origin, *direction),
+            &Interval::new(0.001, f64::INFINITY),
+        ) else {
+            return 0.0;
+        };
+        let distance_squared = hit.t * hit.t * direction.length_squared();
+        let cosine = (direction.unit().dot(&self.normal)).abs();
+        distance_squared / (cosine * surface.area)
+    }
 
-        Some(*result)
+    fn random_direction(&self, origin: &Vec3) -> Vec3 {
+        let Some(surface) = &self.surface else {
+            return Vec3::new(1.0, 0.0, 0.0);
+        };
+        let point = surface.origin + surface.e1 * random::<f64>() + surface.e2 * random::<f64>();
+        point - *origin
     }
 }
@@ -1,13 +1,18 @@
+use std::f64::consts::PI;
 use std::sync::Arc;
 
+use crate::aabb::Aabb;
 use crate::hit::{HitRecord, Hittable};
 use crate::interval::Interval;
 use crate::material::materials::Material;
 use crate::ray::Ray;
+use crate::rng::random;
 use crate::vec3::Vec3;
 
 pub struct Sphere {
+    // position at time 0, the sphere moves by `motion` until time 1
     pub center: Vec3,
+    pub motion: Vec3,
     pub radius: f64,
     pub material: Arc<dyn Material>
 }
@@ -16,15 +21,44 @@ impl Sphere {
     pub fn new(center: Vec3, radius: f64, material: Arc<dyn Material>) -> Self {
         Sphere {
             center,
+            motion: Vec3::new_zero(),
             radius,
             material,
         }
     }
+
+    /// Sphere moving linearly from `center0` at time 0 to `center1` at
+    /// time 1. It rests at either end outside that interval.
+    pub fn new_moving(
+        center0: Vec3,
+        center1: Vec3,
+        radius: f64,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        Sphere {
+            center: center0,
+            motion: center1 - center0,
+            radius,
+            material,
+        }
+    }
+
+    pub fn center_at(&self, time: f64) -> Vec3 {
+        self.center + self.motion * time.clamp(0.0, 1.0)
+    }
+
+    // u: angle around the y axis starting from -x, v: angle from -y to +y
+    pub fn uv(p: &Vec3) -> (f64, f64) {
+        let theta = (-p.y).clamp(-1.0, 1.0).acos();
+        let phi = (-p.z).atan2(p.x) + PI;
+        (phi / (2.0 * PI), theta / PI)
+    }
 }
 
 impl Hittable for Sphere {
     fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord> {
-        let oc = ray.origin - self.center;
+        let center = self.center_at(ray.time);
+        let oc = ray.origin - center;
         let a = ray.direction.length_squared();
         let half_b = oc.dot(&ray.direction);
         let c = oc.length_squared() - self.radius * self.radius;
@@ -45,14 +79,68 @@ impl Hittable for Sphere {
 
         let mut result = Box::new(HitRecord::new(
             ray.at(root),
-            (ray.at(root) - self.center) / self.radius,
+            (ray.at(root) - center) / self.radius,
             root,
             Arc::clone(&self.material),
         ));
         
-        let outward_normal = (result.p - self.center) / self.radius;
+        let outward_normal = (result.p - center) / self.radius;
         result.set_face_normal(ray, &outward_normal);
+        (result.u, result.v) = Sphere::uv(&outward_normal);
 
         Some(*result)
     }
+
+    fn bounding_box(&self) -> Aabb {
+        let radius = Vec3::new(self.radius, self.radius, self.radius);
+        let end = self.center + self.motion;
+        Aabb::new_enclosing(
+            &Aabb::new_from_points(self.center - radius, self.center + radius),
+            &Aabb::new_from_points(end - radius, end + radius),
+        )
+    }
+
+    // uniform over the cone of directions the sphere covers, sampled at its
+    // time 0 position
+    fn pdf_value(&self, origin: &Vec3, direction: &Vec3) -> f64 {
+        if self
+            .hit(&Ray::new(*origin, *direction), &Interval::new(0.001, f64::INFINITY))
+            .is_none()
+        {
+            return 0.0;
+        }
+        let distance_squared = (self.center - *origin).length_squared();
+        let radius_squared = self.radius * self.radius;
+        if distance_squared <= radius_squared {
+            // inside, every direction hits
+            return 1.0 / (4.0 * PI);
+        }
+        let cos_theta_max = (1.0 - radius_squared / distance_squared).sqrt();
+        1.0 / (2.0 * PI * (1.0 - cos_theta_max))
+    }
+
+    fn random_direction(&self, origin: &Vec3) -> Vec3 {
+        let direction = self.center - *origin;
+        let distance_squared = direction.length_squared();
+        let radius_squared = self.radius * self.radius;
+        if distance_squared <= radius_squared {
+            return Vec3::random_unit_vector();
+        }
+
+        let cos_theta_max = (1.0 - radius_squared / distance_squared).sqrt();
+        let z = 1.0 + random::<f64>() * (cos_theta_max - 1.0);
+        let phi = 2.0 * PI * random::<f64>();
+        let sin_theta = (1.0 - z * z).max(0.0).sqrt();
+
+        // orthonormal basis around the direction to the center
+        let w = direction.unit();
+        let a = if w.x.abs() > 0.9 {
+            Vec3::new(0.0, 1.0, 0.0)
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let v = w.cross(&a).unit();
+        let u = w.cross(&v);
+        u * (phi.cos() * sin_theta) + v * (phi.sin() * sin_theta) + w * z
+    }
 }
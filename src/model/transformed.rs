@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use crate::aabb::Aabb;
+use crate::hit::{HitRecord, Hittable};
+use crate::interval::{Interval, UNIVERSE};
+use crate::ray::Ray;
+use crate::transform::Transform;
+use crate::vec3::Vec3;
+
+/// Instance of a shared object placed in the world by `transform`.
+///
+/// The object is held through an `Arc`, so any number of instances can
+/// reference the same geometry (a whole mesh and its BVH included).
+pub struct Transformed<H: Hittable + ?Sized = dyn Hittable> {
+    pub object: Arc<H>,
+    pub transform: Transform,
+    // world space translation added between time 0 and time 1
+    pub motion: Vec3,
+    bbox: Aabb,
+}
+
+impl<H: Hittable + ?Sized> Transformed<H> {
+    pub fn new(object: Arc<H>, transform: Transform) -> Self {
+        Transformed::new_moving(object, transform, Vec3::new_zero())
+    }
+
+    /// Instance placed by `transform` at time 0 that then slides by `motion`
+    /// until time 1, like `Sphere::new_moving`.
+    pub fn new_moving(object: Arc<H>, transform: Transform, motion: Vec3) -> Self {
+        let start = transformed_box(&object.bounding_box(), &transform);
+        let end = transformed_box(&start, &Transform::translate(motion));
+        Transformed {
+            object,
+            transform,
+            motion,
+            bbox: Aabb::new_enclosing(&start, &end),
+        }
+    }
+
+    fn offset_at(&self, time: f64) -> Vec3 {
+        self.motion * time.clamp(0.0, 1.0)
+    }
+}
+
+// box around the eight transformed corners of `bbox`
+fn transformed_box(bbox: &Aabb, transform: &Transform) -> Aabb {
+    if bbox.is_empty() {
+        return *bbox;
+    }
+    let mut min = Vec3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+    let mut max = Vec3::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for i in 0..8 {
+        let corner = Vec3::new(
+            if i & 1 == 0 { bbox.x.min } else { bbox.x.max },
+            if i & 2 == 0 { bbox.y.min } else { bbox.y.max },
+            if i & 4 == 0 { bbox.z.min } else { bbox.z.max },
+        );
+        let p = transform.point(&corner);
+        for axis in 0..3 {
+            min[axis] = min[axis].min(p[axis]);
+            max[axis] = max[axis].max(p[axis]);
+        }
+    }
+    // unbounded objects stay unbounded after rotation
+    let finite = |v: &Vec3| v.x.is_finite() && v.y.is_finite() && v.z.is_finite();
+    if !finite(&min) || !finite(&max) {
+        return Aabb::new(UNIVERSE, UNIVERSE, UNIVERSE);
+    }
+    Aabb::new_from_points(min, max)
+}
+
+impl<H: Hittable + ?Sized> Hittable for Transformed<H> {
+    fn hit(&self, ray: &Ray, interval: &Interval) -> Option<HitRecord> {
+        // the direction is not renormalized, so `t` is the same in both spaces
+        let offset = self.offset_at(ray.time);
+        let local_ray = Ray::new_with_time(
+            self.transform
+                .inverse
+                .transform_point(&(ray.origin - offset)),
+            self.transform.inverse.transform_vector(&ray.direction),
+            ray.time,
+        );
+        let mut result = self.object.hit(&local_ray, interval)?;
+        result.p = self.transform.point(&result.p) + offset;
+        result.normal = self.transform.normal(&result.normal).unit();
+        Some(result)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+}
@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
+use crate::aabb::Aabb;
 use crate::hit::{HitRecord, Hittable};
 use crate::interval::Interval;
 use crate::material::materials::Material;
 use crate::ray::Ray;
+use crate::rng::random;
 use crate::vec3::Vec3;
 
 pub struct Triangle {
@@ -11,20 +13,23 @@ pub struct Triangle {
     pub b: Vec3,
     pub c: Vec3,
     pub material: Arc<dyn Material>,
+    // unit length, facing the side from which a, b, c run counterclockwise
     pub normal: Vec3,
+    area: f64,
 }
 
 impl Triangle {
     pub fn new(a: Vec3, b: Vec3, c: Vec3, material: Arc<dyn Material>) -> Triangle {
         // Normal Vector obeys the right hand rule
         // a x b = c
-        let normal = (b - a).cross(&(c - a));
+        let cross = (b - a).cross(&(c - a));
         Triangle {
             a,
             b,
             c,
             material,
-            normal,
+            normal: cross.unit(),
+            area: cross.length() / 2.0,
         }
     }
 }
@@ -43,7 +48,7 @@ impl Hittable for Triangle {
         let f = 1.0 / a;
         let s = ray.origin - self.a;
         let u = f * s.dot(&h);
-        if u < 0.0 || u > 1.0 {
+        if !(0.0..=1.0).contains(&u) {
             return None;
         }
         let q = s.cross(&edge1);
@@ -55,12 +60,42 @@ impl Hittable for Triangle {
         if !interval.contains(t) {
             return None;
         }
-        let result = HitRecord::new(
+        let mut result = HitRecord::new(
             ray.at(t),
             self.normal,
             t,
             Arc::clone(&self.material),
         );
+        result.set_face_normal(ray, &self.normal);
+        // barycentric coordinates double as texture coordinates
+        (result.u, result.v) = (u, v);
         Some(result)
     }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::new_enclosing(
+            &Aabb::new_from_points(self.a, self.b),
+            &Aabb::new_from_points(self.a, self.c),
+        )
+    }
+
+    // uniform over the triangle's area, converted to solid angle
+    fn pdf_value(&self, origin: &Vec3, direction: &Vec3) -> f64 {
+        let Some(hit) = self.hit(
+            &Ray::new(*origin, *direction),
+            &Interval::new(0.001, f64::INFINITY),
+        ) else {
+            return 0.0;
+        };
+        let distance_squared = hit.t * hit.t * direction.length_squared();
+        let cosine = (direction.unit().dot(&self.normal)).abs();
+        distance_squared / (cosine * self.area)
+    }
+
+    fn random_direction(&self, origin: &Vec3) -> Vec3 {
+        let s = random::<f64>().sqrt();
+        let r = random::<f64>();
+        let point = self.a * (1.0 - s) + self.b * (s * (1.0 - r)) + self.c * (s * r);
+        point - *origin
+    }
 }
@@ -0,0 +1,93 @@
+use crate::{interval::Interval, vec3::Vec3};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToneMapping {
+    // hard clip at 1.0
+    Clamp,
+    // x / (1 + x), keeps highlights from clipping
+    Reinhard,
+    // Narkowicz's fit of the ACES filmic reference curve
+    AcesFilmic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferFunction {
+    Linear,
+    Gamma(f64),
+    Srgb,
+}
+
+/// Turns linear radiance from the film into displayable 8-bit pixels.
+#[derive(Debug, Clone, Copy)]
+pub struct PostProcess {
+    // exposure compensation in stops, radiance is scaled by 2^exposure
+    pub exposure: f64,
+    pub tone_mapping: ToneMapping,
+    pub transfer: TransferFunction,
+}
+
+impl Default for PostProcess {
+    fn default() -> Self {
+        PostProcess {
+            exposure: 0.0,
+            tone_mapping: ToneMapping::Clamp,
+            transfer: TransferFunction::Srgb,
+        }
+    }
+}
+
+impl ToneMapping {
+    pub fn map(&self, x: f64) -> f64 {
+        let x = x.max(0.0);
+        match self {
+            ToneMapping::Clamp => x.min(1.0),
+            ToneMapping::Reinhard => x / (1.0 + x),
+            ToneMapping::AcesFilmic => {
+                let (a, b, c, d, e) = (2.51, 0.03, 2.43, 0.59, 0.14);
+                ((x * (a * x + b)) / (x * (c * x + d) + e)).clamp(0.0, 1.0)
+            }
+        }
+    }
+}
+
+impl TransferFunction {
+    pub fn encode(&self, x: f64) -> f64 {
+        match self {
+            TransferFunction::Linear => x,
+            TransferFunction::Gamma(gamma) => x.powf(1.0 / gamma),
+            TransferFunction::Srgb => {
+                if x <= 0.0031308 {
+                    12.92 * x
+                } else {
+                    1.055 * x.powf(1.0 / 2.4) - 0.055
+                }
+            }
+        }
+    }
+}
+
+impl PostProcess {
+    pub fn new(exposure: f64, tone_mapping: ToneMapping, transfer: TransferFunction) -> Self {
+        PostProcess {
+            exposure,
+            tone_mapping,
+            transfer,
+        }
+    }
+
+    pub fn apply(&self, color: &Vec3) -> [u8; 3] {
+        let scale = 2f64.powf(self.exposure);
+        let intv = Interval::new(0.0, 0.999);
+        let channel = |x: f64| {
+            let mapped = self.tone_mapping.map(x * scale);
+            (256.0 * intv.clamp(self.transfer.encode(mapped))) as u8
+        };
+        [channel(color.x), channel(color.y), channel(color.z)]
+    }
+
+    pub fn develop(&self, film: &[Vec<Vec3>]) -> Vec<Vec<[u8; 3]>> {
+        film.iter()
+            .map(|row| row.iter().map(|pixel| self.apply(pixel)).collect())
+            .collect()
+    }
+}
@@ -0,0 +1,88 @@
+use std::f64::consts::PI;
+
+use crate::vec3::Vec3;
+
+/// How the camera maps positions on the image to rays.
+///
+/// Directions are given in camera space: x to the right, y up and the view
+/// direction along -z.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Projection {
+    /// Pinhole or thin lens camera with the vertical field of view `vfov`.
+    Perspective,
+    /// Parallel rays along the view direction, `height` world units of the
+    /// scene fit the image vertically.
+    Orthographic { height: f64 },
+    /// Equidistant fisheye, the angle to the view direction grows linearly
+    /// towards the edge of an image circle that spans `fov` degrees and
+    /// touches the shorter image side. Pixels outside the circle stay black.
+    Fisheye { fov: f64 },
+    /// Full 360 by 180 degree panorama, longitude across the image and
+    /// latitude down it, the view direction in the center.
+    Equirectangular,
+    /// Six 90 degree views in a 3x2 grid, +x -x +y on top and -y +z -z
+    /// below. Faces are square when the image is 3:2.
+    CubeMap,
+}
+
+impl Projection {
+    // Direction through the image position (s, t), both in [0, 1] with t
+    // growing downwards. Only for the panoramic projections, perspective and
+    // orthographic rays go through the camera's pixel grid instead.
+    pub fn direction(&self, s: f64, t: f64, aspect_ratio: f64) -> Option<Vec3> {
+        match *self {
+            Projection::Fisheye { fov } => {
+                // the shorter image side spans [-1, 1]
+                let (mut x, mut y) = (2.0 * s - 1.0, 1.0 - 2.0 * t);
+                if aspect_ratio >= 1.0 {
+                    x *= aspect_ratio;
+                } else {
+                    y /= aspect_ratio;
+                }
+                let r = (x * x + y * y).sqrt();
+                if r > 1.0 {
+                    return None;
+                }
+                let theta = r * fov.to_radians() / 2.0;
+                let phi = y.atan2(x);
+                Some(Vec3::new(
+                    theta.sin() * phi.cos(),
+                    theta.sin() * phi.sin(),
+                    -theta.cos(),
+                ))
+            }
+            Projection::Equirectangular => {
+                let longitude = (s - 0.5) * 2.0 * PI;
+                let latitude = (0.5 - t) * PI;
+                Some(Vec3::new(
+                    latitude.cos() * longitude.sin(),
+                    latitude.sin(),
+                    -latitude.cos() * longitude.cos(),
+                ))
+            }
+            Projection::CubeMap => {
+                let col = ((s * 3.0) as usize).min(2);
+                let row = ((t * 2.0) as usize).min(1);
+                // position on the face, a to the right and b downwards
+                let a = 2.0 * (s * 3.0 - col as f64) - 1.0;
+                let b = 2.0 * (t * 2.0 - row as f64) - 1.0;
+                // each face as seen by turning the camera from the forward view
+                let direction = match row * 3 + col {
+                    0 => Vec3::new(1.0, -b, a),
+                    1 => Vec3::new(-1.0, -b, -a),
+                    2 => Vec3::new(a, 1.0, -b),
+                    3 => Vec3::new(a, -1.0, b),
+                    4 => Vec3::new(-a, -b, 1.0),
+                    _ => Vec3::new(a, -b, -1.0),
+                };
+                Some(direction.unit())
+            }
+            Projection::Perspective | Projection::Orthographic { .. } => None,
+        }
+    }
+
+    // depth of field needs a focus plane or sphere in front of the lens
+    pub fn has_focus(&self) -> bool {
+        !matches!(self, Projection::Equirectangular | Projection::CubeMap)
+    }
+}
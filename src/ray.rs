@@ -2,12 +2,18 @@ use crate::vec3::Vec3;
 
 pub struct Ray {
     pub origin: Vec3,
-    pub direction: Vec3
+    pub direction: Vec3,
+    // moment the ray was cast, within the camera's shutter interval
+    pub time: f64,
 }
 
 impl Ray {
     pub fn new(origin: Vec3, direction: Vec3) -> Self {
-        Ray { origin, direction }
+        Ray { origin, direction, time: 0.0 }
+    }
+
+    pub fn new_with_time(origin: Vec3, direction: Vec3, time: f64) -> Self {
+        Ray { origin, direction, time }
     }
 
     pub fn at(&self, t: f64) -> Vec3 {
@@ -1,76 +1,169 @@
-use crate::{camera::CameraConfig, hit::HittableList, interval::Interval, ray::Ray, vec3::Vec3};
+use std::f64::consts::PI;
+
+use crate::{
+    aperture::vignetted,
+    camera::CameraConfig,
+    hit::{Hittable, HittableList},
+    interval::Interval,
+    projection::Projection,
+    ray::Ray,
+    rng::{self, random},
+    vec3::Vec3,
+};
 use indicatif::ProgressBar;
-use rand::random;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 
-pub fn render_pixel(col: u32, row: u32, world: &HittableList, config: &CameraConfig) -> [u8; 3] {
+pub fn render_pixel(col: u32, row: u32, world: &HittableList, config: &CameraConfig) -> Vec3 {
+    if let Some(seed) = config.seed {
+        // reseed per pixel so the image does not depend on thread scheduling
+        let index = row as u64 * config.image_size.0 as u64 + col as u64;
+        rng::seed(rng::mix_seed(seed, index));
+    }
+
     let mut pixel_color = Vec3::new_zero();
 
     // repeat random sample to achieve anti-aliasing
     for _ in 0..config.samples_per_pixel {
-        let ray = get_ray(col, row, config);
-        pixel_color += ray_color(&ray, config.max_depth, world);
+        if let Some(ray) = get_ray(col, row, config) {
+            pixel_color += config.integrator.radiance(ray, world, config);
+        }
     }
 
-    let this = &pixel_color;
-    let sample_per_pixel = config.samples_per_pixel;
-    let (mut r, mut g, mut b) = (this.x, this.y, this.z);
-
-    let scale = 1.0 / sample_per_pixel as f64;
-    r *= scale;
-    g *= scale;
-    b *= scale;
-
-    let intv = Interval::new(0.0, 0.999);
-    [
-        (256.0 * intv.clamp(r)) as u8,
-        (256.0 * intv.clamp(g)) as u8,
-        (256.0 * intv.clamp(b)) as u8,
-    ]
+    // linear radiance, tone mapping happens when the film is developed
+    pixel_color / config.samples_per_pixel as f64
 }
 
-fn defocus_disk_sample(config: &CameraConfig) -> Vec3 {
-    let p = Vec3::random_in_unit_disk();
-    config.center + (config.defocus_u * p.x) + (config.defocus_v * p.y)
+// `None` when the lens barrel blocks the sample, (s, t) is the position in the view
+fn defocus_disk_sample(center: Vec3, config: &CameraConfig, s: f64, t: f64) -> Option<Vec3> {
+    let p = config.aperture.sample();
+    if config.vignetting > 0.0 {
+        let (width, height) = config.view_size();
+        let (width, height) = (width as f64, height as f64);
+        let half_diagonal = 0.5 * (width * width + height * height).sqrt();
+        let film = Vec3::new((s - 0.5) * width, (0.5 - t) * height, 0.0) / half_diagonal;
+        if vignetted(&p, &film, config.vignetting) {
+            return None;
+        }
+    }
+    Some(center + (config.defocus_u * p.x) + (config.defocus_v * p.y))
 }
 
-pub fn get_ray(u: u32, v: u32, config: &CameraConfig) -> Ray {
+// `None` where the projection does not cover the image, such pixels stay black
+pub fn get_ray(u: u32, v: u32, config: &CameraConfig) -> Option<Ray> {
+    let (basis_u, basis_v, basis_w) = config.basis;
+    // each eye sees its part of the image from a center moved along `basis_u`
+    let (u, v, eye_offset) = match &config.stereo {
+        Some(stereo) => {
+            let (eye, u, v) = stereo.eye(u, v, config.image_size);
+            (u, v, eye * stereo.ipd / 2.0)
+        }
+        None => (u, v, 0.0),
+    };
+    let center = config.center + basis_u * eye_offset;
+
     let mut pixel_center =
         config.pixel00_loc + (config.pixel_delta_u * u as f64) + (config.pixel_delta_v * v as f64);
+    if let Some(stereo) = &config.stereo {
+        pixel_center += basis_u * (eye_offset * stereo.grid_shift(config.focus_dist));
+    }
     let (px, py) = (-0.5 + random::<f64>(), -0.5 + random::<f64>());
     pixel_center += (config.pixel_delta_u * px) + (config.pixel_delta_v * py);
+    // position within the view, both in [0, 1] and t growing downwards
+    let (width, height) = config.view_size();
+    let s = (u as f64 + 0.5 + px) / width as f64;
+    let t = (v as f64 + 0.5 + py) / height as f64;
 
-    let ray_origin = if config.defocus_angle <= 0.0 {
-        config.center
+    let ray_origin = if config.defocus_angle <= 0.0 || !config.projection.has_focus() {
+        center
     } else {
-        defocus_disk_sample(config)
+        defocus_disk_sample(center, config, s, t)?
     };
-    let ray_direction = pixel_center - ray_origin;
+    let (ray_origin, ray_direction) = match config.projection {
+        Projection::Perspective => (ray_origin, pixel_center - ray_origin),
+        Projection::Orthographic { .. } => {
+            // parallel rays start on the plane through the camera center,
+            // the lens offset moves them off it
+            let origin = pixel_center + basis_w * config.focus_dist + (ray_origin - center);
+            (origin, pixel_center - origin)
+        }
+        projection => {
+            let aspect_ratio = width as f64 / height as f64;
+            let direction = projection.direction(s, t, aspect_ratio)?;
+            let direction = basis_u * direction.x + basis_v * direction.y + basis_w * direction.z;
+            let Some(stereo) = &config.stereo else {
+                // in focus on a sphere of radius `focus_dist` around the camera
+                return Some(ray_at_time(
+                    ray_origin,
+                    center + direction * config.focus_dist - ray_origin,
+                    config,
+                ));
+            };
 
-    Ray::new(ray_origin, ray_direction)
+            let eye = if projection == Projection::Equirectangular {
+                // ODS, the eyes turn with the longitude around the rig center
+                let longitude = (s - 0.5) * 2.0 * PI;
+                config.center + (basis_u * longitude.cos() + basis_w * longitude.sin()) * eye_offset
+            } else {
+                center
+            };
+            // both eyes look at the point `convergence` away from the rig center
+            let convergence = stereo.convergence(config.focus_dist);
+            let direction = if convergence.is_finite() {
+                (config.center + direction * convergence - eye).unit()
+            } else {
+                direction
+            };
+            let ray_origin = ray_origin - center + eye;
+            (ray_origin, eye + direction * config.focus_dist - ray_origin)
+        }
+    };
+
+    Some(ray_at_time(ray_origin, ray_direction, config))
 }
 
-pub fn ray_color(ray: &Ray, depth: u32, world: &HittableList) -> Vec3 {
-    if depth == 0 {
-        // exceeded recursion
-        return Vec3::new_zero();
-    }
-    if let Some(hit_record) = world.hit(ray, &Interval::new(0.001, f64::INFINITY)) {
-        if let Some((attenutation, scatter)) = hit_record.material.scatter(ray, &hit_record) {
-            return ray_color(&scatter, depth - 1, world) * attenutation;
+// Distance at which the scene seen through (s, t) is in focus, both in [0, 1]
+// with t growing downwards. Casts a pinhole ray from the center of the camera
+// (or stereo rig), `None` when it misses everything.
+pub fn focus_distance(s: f64, t: f64, world: &HittableList, config: &CameraConfig) -> Option<f64> {
+    let (basis_u, basis_v, basis_w) = config.basis;
+    let (width, height) = config.view_size();
+    let viewport_point = config.pixel00_loc
+        + config.pixel_delta_u * (s * width as f64 - 0.5)
+        + config.pixel_delta_v * (t * height as f64 - 0.5);
+    let (origin, direction) = match config.projection {
+        Projection::Perspective => (config.center, viewport_point - config.center),
+        Projection::Orthographic { .. } => {
+            let origin = viewport_point + basis_w * config.focus_dist;
+            (origin, viewport_point - origin)
         }
-        return Vec3::new_zero();
+        projection => {
+            let direction = projection.direction(s, t, width as f64 / height as f64)?;
+            let direction = basis_u * direction.x + basis_v * direction.y + basis_w * direction.z;
+            (config.center, direction)
+        }
+    };
+    let (open, close) = config.shutter;
+    let ray = Ray::new_with_time(origin, direction, 0.5 * (open + close));
+    let hit_record = world.hit(&ray, &Interval::new(0.001, f64::INFINITY))?;
+
+    // planar projections focus on a plane, the panoramic ones on a sphere
+    let offset = hit_record.p - origin;
+    match config.projection {
+        Projection::Perspective | Projection::Orthographic { .. } => Some(-offset.dot(&basis_w)),
+        _ => Some(offset.length()),
     }
+}
 
-    // background color
-    let unit_direction = ray.direction.unit();
-    let a = 0.5 * (unit_direction.y + 1.0);
-    Vec3::new(1.0, 1.0, 1.0) * (1.0 - a) + Vec3::new(0.5, 0.7, 1.0) * a
+// the ray is cast at a random time while the shutter is open
+fn ray_at_time(origin: Vec3, direction: Vec3, config: &CameraConfig) -> Ray {
+    let (open, close) = config.shutter;
+    let ray_time = open + (close - open) * random::<f64>();
+    Ray::new_with_time(origin, direction, ray_time)
 }
 
-pub fn multithread_render(world: &HittableList, config: &CameraConfig) -> Vec<Vec<[u8; 3]>> {
+pub fn multithread_render(world: &HittableList, config: &CameraConfig) -> Vec<Vec<Vec3>> {
     let mut image =
-        vec![vec![[0u8; 3]; config.image_size.0 as usize]; config.image_size.1 as usize];
+        vec![vec![Vec3::new_zero(); config.image_size.0 as usize]; config.image_size.1 as usize];
     let row_iter: Vec<_> = image.chunks_exact_mut(1).enumerate().collect();
     let progress_bar = ProgressBar::new(config.image_size.1 as u64);
     if config.disable_progress_bar {
@@ -87,9 +180,9 @@ pub fn multithread_render(world: &HittableList, config: &CameraConfig) -> Vec<Ve
     image
 }
 
-pub fn singlethread_render(world: &HittableList, config: &CameraConfig) -> Vec<Vec<[u8; 3]>> {
+pub fn singlethread_render(world: &HittableList, config: &CameraConfig) -> Vec<Vec<Vec3>> {
     let mut image =
-        vec![vec![[0u8; 3]; config.image_size.0 as usize]; config.image_size.1 as usize];
+        vec![vec![Vec3::new_zero(); config.image_size.0 as usize]; config.image_size.1 as usize];
     let progress_bar = ProgressBar::new(config.image_size.1 as u64);
     if config.disable_progress_bar {
         progress_bar.set_draw_target(indicatif::ProgressDrawTarget::hidden());
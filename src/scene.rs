@@ -0,0 +1,1026 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde::Deserialize;
+use toml::Spanned;
+
+use crate::{
+    aperture::{Aperture, ApertureMask},
+    bvh::BvhNode,
+    camera::CameraSettings,
+    environment::{Environment, ImageEnvironment, SkyGradient, SolidBackground},
+    hit::{Hittable, HittableList},
+    material::materials::{
+        Dielectric, DiffuseLight, Isotropic, Lambertian, Material, Metal, Mirror,
+    },
+    model::{
+        constant_medium::ConstantMedium, cuboid::Cuboid, mesh::TriangleMesh, obj::load_obj,
+        quad::Quad, rectangle::Rectangle, sphere::Sphere, transformed::Transformed,
+        triangle::Triangle,
+    },
+    projection::Projection,
+    stereo::{Stereo, StereoLayout},
+    texture::textures::{
+        CheckerTexture, ImageTexture, MarbleTexture, NoiseTexture, SolidColor, Texture,
+        TurbulenceTexture, WoodTexture, WorleyTexture,
+    },
+    transform::Transform,
+    vec3::Vec3,
+};
+
+/// A world and the camera looking at it, as described by a scene file.
+pub struct Scene {
+    pub world: HittableList,
+    // emissive primitives of `world`, for light sampling
+    pub lights: HittableList,
+    pub camera: CameraSettings,
+    // seen by rays that miss `world`, `None` keeps the camera's sky
+    pub environment: Option<Arc<dyn Environment>>,
+}
+
+#[derive(Debug)]
+pub struct SceneError {
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl Display for SceneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "line {}, column {}: {}", line, column, self.message)
+            }
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SceneDesc {
+    camera: CameraDesc,
+    #[serde(default)]
+    textures: BTreeMap<String, TextureDesc>,
+    #[serde(default)]
+    materials: BTreeMap<String, MaterialDesc>,
+    #[serde(default)]
+    objects: Vec<ObjectDesc>,
+    environment: Option<EnvironmentDesc>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CameraDesc {
+    width: Spanned<u32>,
+    height: Spanned<u32>,
+    output: Option<String>,
+    max_depth: Option<u32>,
+    samples_per_pixel: Option<Spanned<u32>>,
+    vfov: Option<Spanned<f64>>,
+    look_from: Spanned<[f64; 3]>,
+    look_at: Spanned<[f64; 3]>,
+    vup: Option<Spanned<[f64; 3]>>,
+    defocus_angle: Option<Spanned<f64>>,
+    focus_dist: Option<Spanned<f64>>,
+    // moving objects travel from time 0 to time 1
+    shutter_open: Option<Spanned<f64>>,
+    shutter_close: Option<Spanned<f64>>,
+    // bounces before Russian roulette may end a path
+    russian_roulette: Option<u32>,
+    // perspective, orthographic, fisheye, equirectangular or cubemap
+    projection: Option<Spanned<String>>,
+    // world units visible vertically, defaults to the perspective view at focus_dist
+    ortho_height: Option<Spanned<f64>>,
+    fisheye_fov: Option<Spanned<f64>>,
+    // side_by_side or top_bottom, renders a left and a right eye view
+    stereo: Option<Spanned<String>>,
+    ipd: Option<Spanned<f64>>,
+    // follows focus_dist by default, also when autofocus sets it, so objects
+    // in focus appear at screen depth. inf keeps the views parallel
+    convergence: Option<Spanned<f64>>,
+    // circle, polygon or mask, shapes the bokeh of a defocused camera
+    aperture: Option<Spanned<String>>,
+    blades: Option<Spanned<u32>>,
+    // degrees
+    blade_rotation: Option<Spanned<f64>>,
+    // grayscale image for the mask aperture
+    aperture_mask: Option<Spanned<String>>,
+    vignetting: Option<Spanned<f64>>,
+    // true to focus on the image center, or a point on the image from [0, 0]
+    // top left to [1, 1] bottom right. focus_dist is used when it sees nothing
+    autofocus: Option<Spanned<AutofocusDesc>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AutofocusDesc {
+    Enabled(bool),
+    At([f64; 2]),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EnvironmentDesc {
+    #[serde(rename = "type")]
+    kind: Spanned<String>,
+    // solid
+    color: Option<Spanned<[f64; 3]>>,
+    // gradient, defaults to the white-to-blue sky
+    horizon: Option<Spanned<[f64; 3]>>,
+    zenith: Option<Spanned<[f64; 3]>>,
+    // image, an equirectangular (HDR) map
+    path: Option<Spanned<String>>,
+    intensity: Option<Spanned<f64>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TextureDesc {
+    #[serde(rename = "type")]
+    kind: Spanned<String>,
+    // solid
+    color: Option<Spanned<[f64; 3]>>,
+    // checker
+    frequency: Option<Spanned<f64>>,
+    even: Option<Spanned<[f64; 3]>>,
+    odd: Option<Spanned<[f64; 3]>>,
+    // image
+    path: Option<Spanned<String>>,
+    // noise, turbulence, marble, wood, worley
+    seed: Option<Spanned<u64>>,
+    scale: Option<Spanned<f64>>,
+    octaves: Option<Spanned<u32>>,
+    strength: Option<Spanned<f64>>,
+    rings: Option<Spanned<f64>>,
+    low: Option<Spanned<[f64; 3]>>,
+    high: Option<Spanned<[f64; 3]>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MaterialDesc {
+    #[serde(rename = "type")]
+    kind: Spanned<String>,
+    albedo: Option<Spanned<[f64; 3]>>,
+    // name of a texture, replaces `albedo`
+    texture: Option<Spanned<String>>,
+    fuzz: Option<Spanned<f64>>,
+    ir: Option<Spanned<f64>>,
+    emit: Option<Spanned<[f64; 3]>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ObjectDesc {
+    #[serde(rename = "type")]
+    kind: Spanned<String>,
+    material: Spanned<String>,
+    // sphere
+    center: Option<Spanned<[f64; 3]>>,
+    radius: Option<Spanned<f64>>,
+    // position at time 1 for a moving sphere
+    center1: Option<Spanned<[f64; 3]>>,
+    // triangle, cuboid (two opposite corners `a` and `b`)
+    a: Option<Spanned<[f64; 3]>>,
+    b: Option<Spanned<[f64; 3]>>,
+    c: Option<Spanned<[f64; 3]>>,
+    // rectangle
+    left_up: Option<Spanned<[f64; 3]>>,
+    right_down: Option<Spanned<[f64; 3]>>,
+    normal: Option<Spanned<[f64; 3]>>,
+    // quad
+    origin: Option<Spanned<[f64; 3]>>,
+    u: Option<Spanned<[f64; 3]>>,
+    v: Option<Spanned<[f64; 3]>>,
+    // mesh, `material` is used for faces without an MTL material
+    path: Option<Spanned<String>>,
+    // placement of any object: scaled, then rotated around x, y and z
+    // (degrees), then translated
+    scale: Option<Spanned<[f64; 3]>>,
+    rotate: Option<Spanned<[f64; 3]>>,
+    translate: Option<Spanned<[f64; 3]>>,
+    // translation added between time 0 and time 1
+    motion: Option<Spanned<[f64; 3]>>,
+    // fills the (convex) object with a medium of this density instead,
+    // `material` is its phase function
+    density: Option<Spanned<f64>>,
+}
+
+struct Loader<'a> {
+    source: &'a str,
+    // relative asset paths are resolved against the scene file's directory
+    base_dir: PathBuf,
+    // every instance of the same mesh file and material shares one mesh
+    meshes: RefCell<HashMap<(String, String), Arc<TriangleMesh>>>,
+}
+
+impl Loader<'_> {
+    fn error(&self, span: Range<usize>, message: String) -> SceneError {
+        let start = span.start.min(self.source.len());
+        let before = &self.source[..start];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+        SceneError {
+            message,
+            line: Some(line),
+            column: Some(column),
+        }
+    }
+
+    fn required<'b, T>(
+        &self,
+        value: &'b Option<Spanned<T>>,
+        kind: &Spanned<String>,
+        field: &str,
+    ) -> Result<&'b Spanned<T>, SceneError> {
+        value.as_ref().ok_or_else(|| {
+            self.error(
+                kind.span(),
+                format!("{} is required for type `{}`", field, kind.get_ref()),
+            )
+        })
+    }
+
+    fn vec3(&self, value: &Spanned<[f64; 3]>, field: &str) -> Result<Vec3, SceneError> {
+        let [x, y, z] = *value.get_ref();
+        if !(x.is_finite() && y.is_finite() && z.is_finite()) {
+            return Err(self.error(value.span(), format!("{} must be finite", field)));
+        }
+        Ok(Vec3::new(x, y, z))
+    }
+
+    fn color(&self, value: &Spanned<[f64; 3]>, field: &str) -> Result<Vec3, SceneError> {
+        let color = self.vec3(value, field)?;
+        if color.x < 0.0 || color.y < 0.0 || color.z < 0.0 {
+            return Err(self.error(value.span(), format!("{} must not be negative", field)));
+        }
+        Ok(color)
+    }
+
+    fn positive(
+        &self,
+        value: &Option<Spanned<f64>>,
+        field: &str,
+        default: f64,
+    ) -> Result<f64, SceneError> {
+        match value {
+            Some(v) if !(*v.get_ref() > 0.0 && v.get_ref().is_finite()) => {
+                Err(self.error(v.span(), format!("{} must be positive", field)))
+            }
+            Some(v) => Ok(*v.get_ref()),
+            None => Ok(default),
+        }
+    }
+
+    fn texture(&self, name: &str, desc: &TextureDesc) -> Result<Arc<dyn Texture>, SceneError> {
+        let field = |f: &str| format!("textures.{}.{}", name, f);
+        let texture: Arc<dyn Texture> = match desc.kind.get_ref().as_str() {
+            "solid" => {
+                let color = self.required(&desc.color, &desc.kind, &field("color"))?;
+                SolidColor::new(self.color(color, &field("color"))?)
+            }
+            "checker" => {
+                let even = self.required(&desc.even, &desc.kind, &field("even"))?;
+                let odd = self.required(&desc.odd, &desc.kind, &field("odd"))?;
+                let frequency = self.positive(&desc.frequency, &field("frequency"), 10.0)?;
+                CheckerTexture::new_colors(
+                    frequency,
+                    self.color(even, &field("even"))?,
+                    self.color(odd, &field("odd"))?,
+                )
+            }
+            "image" => {
+                let path = self.required(&desc.path, &desc.kind, &field("path"))?;
+                ImageTexture::load(self.base_dir.join(path.get_ref()))
+                    .map_err(|err| self.error(path.span(), format!("{}: {}", field("path"), err)))?
+            }
+            "noise" | "turbulence" | "marble" | "wood" | "worley" => {
+                let seed = desc.seed.as_ref().map_or(0, |seed| *seed.get_ref());
+                let scale = self.positive(&desc.scale, &field("scale"), 1.0)?;
+                let low = match &desc.low {
+                    Some(low) => self.color(low, &field("low"))?,
+                    None => Vec3::new_zero(),
+                };
+                let high = match &desc.high {
+                    Some(high) => self.color(high, &field("high"))?,
+                    None => Vec3::new(1.0, 1.0, 1.0),
+                };
+                // layers of turbulence, only the turbulent textures have them
+                let octaves = |default: u32| match &desc.octaves {
+                    Some(octaves) if *octaves.get_ref() == 0 => Err(self.error(
+                        octaves.span(),
+                        format!("{} must be positive", field("octaves")),
+                    )),
+                    Some(octaves) => Ok(*octaves.get_ref()),
+                    None => Ok(default),
+                };
+                match desc.kind.get_ref().as_str() {
+                    "turbulence" => TurbulenceTexture::new(seed, scale, octaves(7)?, low, high),
+                    "marble" => {
+                        let strength = self.positive(&desc.strength, &field("strength"), 10.0)?;
+                        MarbleTexture::new(seed, scale, octaves(7)?, strength, low, high)
+                    }
+                    "wood" => {
+                        let rings = self.positive(&desc.rings, &field("rings"), 4.0)?;
+                        let strength = self.positive(&desc.strength, &field("strength"), 0.5)?;
+                        WoodTexture::new(seed, scale, octaves(4)?, rings, strength, low, high)
+                    }
+                    kind => {
+                        if let Some(octaves) = &desc.octaves {
+                            return Err(self.error(
+                                octaves.span(),
+                                format!("{} is not used by type `{}`", field("octaves"), kind),
+                            ));
+                        }
+                        if kind == "noise" {
+                            NoiseTexture::new(seed, scale, low, high)
+                        } else {
+                            WorleyTexture::new(seed, scale, low, high)
+                        }
+                    }
+                }
+            }
+            other => {
+                return Err(self.error(
+                    desc.kind.span(),
+                    format!(
+                        "unknown texture type `{}` for textures.{}, expected one of \
+                         solid, checker, image, noise, turbulence, marble, wood, worley",
+                        other, name
+                    ),
+                ))
+            }
+        };
+        Ok(texture)
+    }
+
+    // either a named texture or a plain `albedo` color
+    fn albedo(
+        &self,
+        desc: &MaterialDesc,
+        field: &str,
+        textures: &BTreeMap<String, Arc<dyn Texture>>,
+    ) -> Result<Arc<dyn Texture>, SceneError> {
+        match &desc.texture {
+            Some(name) => textures.get(name.get_ref()).cloned().ok_or_else(|| {
+                self.error(
+                    name.span(),
+                    format!("{}.texture: unknown texture `{}`", field, name.get_ref()),
+                )
+            }),
+            None => {
+                let field = format!("{}.albedo", field);
+                let albedo = self.required(&desc.albedo, &desc.kind, &field)?;
+                Ok(SolidColor::new(self.color(albedo, &field)?))
+            }
+        }
+    }
+
+    fn material(
+        &self,
+        name: &str,
+        desc: &MaterialDesc,
+        textures: &BTreeMap<String, Arc<dyn Texture>>,
+    ) -> Result<Arc<dyn Material>, SceneError> {
+        let field = |f: &str| format!("materials.{}.{}", name, f);
+        let material: Arc<dyn Material> = match desc.kind.get_ref().as_str() {
+            "lambertian" => Lambertian::new_texture(self.albedo(
+                desc,
+                &format!("materials.{}", name),
+                textures,
+            )?),
+            "metal" => {
+                let albedo = self.albedo(desc, &format!("materials.{}", name), textures)?;
+                let fuzz = match &desc.fuzz {
+                    Some(fuzz) if !(0.0..=1.0).contains(fuzz.get_ref()) => {
+                        return Err(self.error(
+                            fuzz.span(),
+                            format!("{} must be within [0, 1]", field("fuzz")),
+                        ));
+                    }
+                    Some(fuzz) => *fuzz.get_ref(),
+                    None => 0.0,
+                };
+                Metal::new_texture(albedo, fuzz)
+            }
+            "dielectric" => {
+                let ir = self.required(&desc.ir, &desc.kind, &field("ir"))?;
+                if !(*ir.get_ref() > 0.0 && ir.get_ref().is_finite()) {
+                    return Err(self.error(ir.span(), format!("{} must be positive", field("ir"))));
+                }
+                Dielectric::new(*ir.get_ref())
+            }
+            "mirror" => Mirror::new(),
+            "isotropic" => Isotropic::new_texture(self.albedo(
+                desc,
+                &format!("materials.{}", name),
+                textures,
+            )?),
+            "diffuse_light" => {
+                let emit = self.required(&desc.emit, &desc.kind, &field("emit"))?;
+                DiffuseLight::new(self.color(emit, &field("emit"))?)
+            }
+            other => {
+                return Err(self.error(
+                    desc.kind.span(),
+                    format!(
+                        "unknown material type `{}` for materials.{}, expected one of \
+                         lambertian, metal, dielectric, mirror, diffuse_light, isotropic",
+                        other, name
+                    ),
+                ))
+            }
+        };
+        Ok(material)
+    }
+
+    fn object(
+        &self,
+        index: usize,
+        desc: &ObjectDesc,
+        materials: &BTreeMap<String, Arc<dyn Material>>,
+    ) -> Result<Arc<dyn Hittable>, SceneError> {
+        let field = |f: &str| format!("objects[{}].{}", index, f);
+
+        let material = match materials.get(desc.material.get_ref()) {
+            Some(material) => Arc::clone(material),
+            None => {
+                return Err(self.error(
+                    desc.material.span(),
+                    format!(
+                        "{}: unknown material `{}`",
+                        field("material"),
+                        desc.material.get_ref()
+                    ),
+                ))
+            }
+        };
+
+        let object: Arc<dyn Hittable> = match desc.kind.get_ref().as_str() {
+            "sphere" => {
+                let center = self.required(&desc.center, &desc.kind, &field("center"))?;
+                let radius = self.required(&desc.radius, &desc.kind, &field("radius"))?;
+                if *radius.get_ref() == 0.0 || !radius.get_ref().is_finite() {
+                    return Err(self.error(
+                        radius.span(),
+                        format!("{} must be finite and non-zero", field("radius")),
+                    ));
+                }
+                let center = self.vec3(center, &field("center"))?;
+                match &desc.center1 {
+                    Some(center1) => {
+                        let center1 = self.vec3(center1, &field("center1"))?;
+                        Arc::new(Sphere::new_moving(
+                            center,
+                            center1,
+                            *radius.get_ref(),
+                            material,
+                        ))
+                    }
+                    None => Arc::new(Sphere::new(center, *radius.get_ref(), material)),
+                }
+            }
+            "triangle" => {
+                let a = self.required(&desc.a, &desc.kind, &field("a"))?;
+                let a = self.vec3(a, &field("a"))?;
+                let b = self.required(&desc.b, &desc.kind, &field("b"))?;
+                let b = self.vec3(b, &field("b"))?;
+                let c = self.required(&desc.c, &desc.kind, &field("c"))?;
+                let c = self.vec3(c, &field("c"))?;
+                if (b - a).cross(&(c - a)).near_zero() {
+                    return Err(self.error(
+                        desc.kind.span(),
+                        format!("objects[{}] is a degenerate triangle", index),
+                    ));
+                }
+                Arc::new(Triangle::new(a, b, c, material))
+            }
+            "rectangle" => {
+                let left_up = self.required(&desc.left_up, &desc.kind, &field("left_up"))?;
+                let right_down =
+                    self.required(&desc.right_down, &desc.kind, &field("right_down"))?;
+                let normal = self.required(&desc.normal, &desc.kind, &field("normal"))?;
+                let left_up = self.vec3(left_up, &field("left_up"))?;
+                let right_down = self.vec3(right_down, &field("right_down"))?;
+                let normal_value = self.vec3(normal, &field("normal"))?;
+                if normal_value.near_zero() {
+                    return Err(self.error(
+                        normal.span(),
+                        format!("{} must not be zero", field("normal")),
+                    ));
+                }
+                Arc::new(Rectangle::new(left_up, right_down, normal_value, material))
+            }
+            "quad" => {
+                let origin = self.required(&desc.origin, &desc.kind, &field("origin"))?;
+                let origin = self.vec3(origin, &field("origin"))?;
+                let u = self.required(&desc.u, &desc.kind, &field("u"))?;
+                let u = self.vec3(u, &field("u"))?;
+                let v = self.required(&desc.v, &desc.kind, &field("v"))?;
+                let v = self.vec3(v, &field("v"))?;
+                if u.cross(&v).near_zero() {
+                    return Err(self.error(
+                        desc.kind.span(),
+                        format!("objects[{}] is a degenerate quad", index),
+                    ));
+                }
+                Arc::new(Quad::new(origin, u, v, material))
+            }
+            "cuboid" => {
+                let a = self.required(&desc.a, &desc.kind, &field("a"))?;
+                let a = self.vec3(a, &field("a"))?;
+                let b = self.required(&desc.b, &desc.kind, &field("b"))?;
+                let b = self.vec3(b, &field("b"))?;
+                let size = a - b;
+                if size.x.abs() < 1e-8 || size.y.abs() < 1e-8 || size.z.abs() < 1e-8 {
+                    return Err(self.error(
+                        desc.kind.span(),
+                        format!("objects[{}] is a flat cuboid", index),
+                    ));
+                }
+                Arc::new(Cuboid::new(a, b, material))
+            }
+            "mesh" => {
+                let path = self.required(&desc.path, &desc.kind, &field("path"))?;
+                let key = (path.get_ref().clone(), desc.material.get_ref().clone());
+                let cached = self.meshes.borrow().get(&key).cloned();
+                match cached {
+                    Some(mesh) => mesh,
+                    None => {
+                        let mesh = load_obj(self.base_dir.join(path.get_ref()), material).map_err(
+                            |err| self.error(path.span(), format!("{}: {}", field("path"), err)),
+                        )?;
+                        let mesh = Arc::new(mesh);
+                        self.meshes.borrow_mut().insert(key, Arc::clone(&mesh));
+                        mesh
+                    }
+                }
+            }
+            other => {
+                return Err(self.error(
+                    desc.kind.span(),
+                    format!(
+                        "unknown object type `{}` for objects[{}], expected one of sphere, \
+                         triangle, rectangle, quad, cuboid, mesh",
+                        other, index
+                    ),
+                ))
+            }
+        };
+
+        let motion = match &desc.motion {
+            Some(motion) => Some(self.vec3(motion, &field("motion"))?),
+            None => None,
+        };
+        let object: Arc<dyn Hittable> = match (self.transform(index, desc)?, motion) {
+            (None, None) => object,
+            (transform, motion) => Arc::new(Transformed::new_moving(
+                object,
+                transform.unwrap_or_else(Transform::identity),
+                motion.unwrap_or_else(Vec3::new_zero),
+            )),
+        };
+
+        // the medium goes around the placed boundary, so density is per
+        // world space unit
+        match &desc.density {
+            Some(density) if !(*density.get_ref() > 0.0 && density.get_ref().is_finite()) => {
+                Err(self.error(
+                    density.span(),
+                    format!("{} must be positive", field("density")),
+                ))
+            }
+            Some(density) => Ok(Arc::new(ConstantMedium::new_phase(
+                object,
+                *density.get_ref(),
+                Arc::clone(&materials[desc.material.get_ref()]),
+            ))),
+            None => Ok(object),
+        }
+    }
+
+    fn transform(&self, index: usize, desc: &ObjectDesc) -> Result<Option<Transform>, SceneError> {
+        let field = |f: &str| format!("objects[{}].{}", index, f);
+        if desc.scale.is_none() && desc.rotate.is_none() && desc.translate.is_none() {
+            return Ok(None);
+        }
+
+        let mut transform = Transform::identity();
+        if let Some(scale) = &desc.scale {
+            let factors = self.vec3(scale, &field("scale"))?;
+            // subnormal factors have no finite inverse, treat them as zero
+            let tiny = |factor: f64| factor.abs() < f64::MIN_POSITIVE;
+            if tiny(factors.x) || tiny(factors.y) || tiny(factors.z) {
+                return Err(self.error(
+                    scale.span(),
+                    format!("{} must not contain zeros", field("scale")),
+                ));
+            }
+            transform = Transform::scale(factors);
+        }
+        if let Some(rotate) = &desc.rotate {
+            let degrees = self.vec3(rotate, &field("rotate"))?;
+            transform = Transform::rotate_z(degrees.z)
+                * Transform::rotate_y(degrees.y)
+                * Transform::rotate_x(degrees.x)
+                * transform;
+        }
+        if let Some(translate) = &desc.translate {
+            transform =
+                Transform::translate(self.vec3(translate, &field("translate"))?) * transform;
+        }
+        Ok(Some(transform))
+    }
+
+    fn environment(&self, desc: &EnvironmentDesc) -> Result<Arc<dyn Environment>, SceneError> {
+        let environment: Arc<dyn Environment> = match desc.kind.get_ref().as_str() {
+            "solid" => {
+                let color = self.required(&desc.color, &desc.kind, "environment.color")?;
+                SolidBackground::new(self.color(color, "environment.color")?)
+            }
+            "gradient" => {
+                let sky = SkyGradient::default_sky();
+                let horizon = match &desc.horizon {
+                    Some(horizon) => self.color(horizon, "environment.horizon")?,
+                    None => sky.horizon,
+                };
+                let zenith = match &desc.zenith {
+                    Some(zenith) => self.color(zenith, "environment.zenith")?,
+                    None => sky.zenith,
+                };
+                SkyGradient::new(horizon, zenith)
+            }
+            "image" => {
+                let path = self.required(&desc.path, &desc.kind, "environment.path")?;
+                let intensity = self.positive(&desc.intensity, "environment.intensity", 1.0)?;
+                ImageEnvironment::load_scaled(self.base_dir.join(path.get_ref()), intensity)
+                    .map_err(|err| self.error(path.span(), format!("environment.path: {}", err)))?
+            }
+            other => {
+                return Err(self.error(
+                    desc.kind.span(),
+                    format!(
+                        "unknown environment type `{}`, expected one of solid, gradient, image",
+                        other
+                    ),
+                ))
+            }
+        };
+        Ok(environment)
+    }
+
+    fn camera(&self, desc: &CameraDesc) -> Result<CameraSettings, SceneError> {
+        let (width, height) = (&desc.width, &desc.height);
+        if *width.get_ref() == 0 {
+            return Err(self.error(width.span(), "camera.width must be positive".to_string()));
+        }
+        if *height.get_ref() == 0 {
+            return Err(self.error(height.span(), "camera.height must be positive".to_string()));
+        }
+
+        let samples_per_pixel = match &desc.samples_per_pixel {
+            Some(samples) if *samples.get_ref() == 0 => {
+                return Err(self.error(
+                    samples.span(),
+                    "camera.samples_per_pixel must be positive".to_string(),
+                ));
+            }
+            Some(samples) => *samples.get_ref(),
+            None => 100,
+        };
+
+        let vfov = match &desc.vfov {
+            Some(vfov) if !(*vfov.get_ref() > 0.0 && *vfov.get_ref() < 180.0) => {
+                return Err(self.error(
+                    vfov.span(),
+                    "camera.vfov must be within (0, 180) degrees".to_string(),
+                ));
+            }
+            Some(vfov) => *vfov.get_ref(),
+            None => 90.0,
+        };
+
+        let look_from = self.vec3(&desc.look_from, "camera.look_from")?;
+        let look_at = self.vec3(&desc.look_at, "camera.look_at")?;
+        if (look_from - look_at).near_zero() {
+            return Err(self.error(
+                desc.look_at.span(),
+                "camera.look_at must differ from camera.look_from".to_string(),
+            ));
+        }
+        let vup = match &desc.vup {
+            Some(vup) => {
+                let value = self.vec3(vup, "camera.vup")?;
+                if value.cross(&(look_from - look_at)).near_zero() {
+                    return Err(self.error(
+                        vup.span(),
+                        "camera.vup must not be parallel to the view direction".to_string(),
+                    ));
+                }
+                value
+            }
+            None => Vec3::new(0.0, 1.0, 0.0),
+        };
+
+        let defocus_angle = match &desc.defocus_angle {
+            Some(angle) if !(*angle.get_ref() >= 0.0 && *angle.get_ref() < 180.0) => {
+                return Err(self.error(
+                    angle.span(),
+                    "camera.defocus_angle must be within [0, 180) degrees".to_string(),
+                ));
+            }
+            Some(angle) => *angle.get_ref(),
+            None => 0.0,
+        };
+        let focus_dist = match &desc.focus_dist {
+            Some(dist) if !(*dist.get_ref() > 0.0 && dist.get_ref().is_finite()) => {
+                return Err(self.error(
+                    dist.span(),
+                    "camera.focus_dist must be positive".to_string(),
+                ));
+            }
+            Some(dist) => *dist.get_ref(),
+            None => (look_from - look_at).length(),
+        };
+
+        let shutter_open = match &desc.shutter_open {
+            Some(t) if !t.get_ref().is_finite() => {
+                return Err(self.error(t.span(), "camera.shutter_open must be finite".to_string()));
+            }
+            Some(t) => *t.get_ref(),
+            None => 0.0,
+        };
+        let shutter_close = match &desc.shutter_close {
+            Some(t) if !(*t.get_ref() >= shutter_open && t.get_ref().is_finite()) => {
+                return Err(self.error(
+                    t.span(),
+                    "camera.shutter_close must be finite and not before camera.shutter_open"
+                        .to_string(),
+                ));
+            }
+            Some(t) => *t.get_ref(),
+            None => shutter_open,
+        };
+
+        let projection = match &desc.projection {
+            None => Projection::Perspective,
+            Some(kind) => match kind.get_ref().as_str() {
+                "perspective" => Projection::Perspective,
+                "orthographic" => {
+                    let fitted = 2.0 * (vfov.to_radians() / 2.0).tan() * focus_dist;
+                    let height =
+                        self.positive(&desc.ortho_height, "camera.ortho_height", fitted)?;
+                    Projection::Orthographic { height }
+                }
+                "fisheye" => {
+                    let fov = match &desc.fisheye_fov {
+                        Some(fov) if !(*fov.get_ref() > 0.0 && *fov.get_ref() <= 360.0) => {
+                            return Err(self.error(
+                                fov.span(),
+                                "camera.fisheye_fov must be within (0, 360] degrees".to_string(),
+                            ));
+                        }
+                        Some(fov) => *fov.get_ref(),
+                        None => 180.0,
+                    };
+                    Projection::Fisheye { fov }
+                }
+                "equirectangular" => Projection::Equirectangular,
+                "cubemap" => Projection::CubeMap,
+                other => {
+                    return Err(self.error(
+                        kind.span(),
+                        format!(
+                            "unknown camera.projection `{}`, expected one of perspective, \
+                             orthographic, fisheye, equirectangular, cubemap",
+                            other
+                        ),
+                    ))
+                }
+            },
+        };
+
+        let stereo = match &desc.stereo {
+            None => None,
+            Some(layout) => {
+                let layout_span = layout.span();
+                let layout = match layout.get_ref().as_str() {
+                    "side_by_side" => StereoLayout::SideBySide,
+                    "top_bottom" => StereoLayout::TopBottom,
+                    other => {
+                        return Err(self.error(
+                            layout.span(),
+                            format!(
+                                "unknown camera.stereo layout `{}`, expected side_by_side or \
+                                 top_bottom",
+                                other
+                            ),
+                        ))
+                    }
+                };
+                let ipd = self.positive(&desc.ipd, "camera.ipd", 0.064)?;
+                // ipd is checked above, what is left to fail is the convergence
+                let convergence = desc.convergence.as_ref().map(|c| *c.get_ref());
+                let stereo = Stereo::new(ipd, convergence, layout).map_err(|err| {
+                    let span = desc.convergence.as_ref().map_or(layout_span, |c| c.span());
+                    self.error(span, err.to_string())
+                })?;
+                Some(stereo)
+            }
+        };
+
+        let aperture = match &desc.aperture {
+            None => Aperture::Circle,
+            Some(kind) => match kind.get_ref().as_str() {
+                "circle" => Aperture::Circle,
+                "polygon" => {
+                    let blades = match &desc.blades {
+                        Some(blades) if *blades.get_ref() < 3 => {
+                            return Err(self.error(
+                                blades.span(),
+                                "camera.blades must be at least 3".to_string(),
+                            ));
+                        }
+                        Some(blades) => *blades.get_ref(),
+                        None => 6,
+                    };
+                    let rotation = match &desc.blade_rotation {
+                        Some(rotation) if !rotation.get_ref().is_finite() => {
+                            return Err(self.error(
+                                rotation.span(),
+                                "camera.blade_rotation must be finite".to_string(),
+                            ));
+                        }
+                        Some(rotation) => *rotation.get_ref(),
+                        None => 0.0,
+                    };
+                    Aperture::Polygon { blades, rotation }
+                }
+                "mask" => {
+                    let path = self.required(&desc.aperture_mask, kind, "camera.aperture_mask")?;
+                    let mask =
+                        ApertureMask::load(self.base_dir.join(path.get_ref())).map_err(|err| {
+                            self.error(path.span(), format!("camera.aperture_mask: {}", err))
+                        })?;
+                    Aperture::Mask(mask)
+                }
+                other => {
+                    return Err(self.error(
+                        kind.span(),
+                        format!(
+                            "unknown camera.aperture `{}`, expected one of circle, polygon, mask",
+                            other
+                        ),
+                    ))
+                }
+            },
+        };
+        let vignetting = match &desc.vignetting {
+            Some(v) if !(*v.get_ref() >= 0.0 && v.get_ref().is_finite()) => {
+                return Err(self.error(
+                    v.span(),
+                    "camera.vignetting must not be negative".to_string(),
+                ));
+            }
+            Some(v) => *v.get_ref(),
+            None => 0.0,
+        };
+
+        let autofocus = match &desc.autofocus {
+            None => None,
+            Some(autofocus) => match autofocus.get_ref() {
+                AutofocusDesc::Enabled(true) => Some((0.5, 0.5)),
+                AutofocusDesc::Enabled(false) => None,
+                AutofocusDesc::At([s, t]) => {
+                    if !((0.0..=1.0).contains(s) && (0.0..=1.0).contains(t)) {
+                        return Err(self.error(
+                            autofocus.span(),
+                            "camera.autofocus must lie within [0, 0] and [1, 1]".to_string(),
+                        ));
+                    }
+                    Some((*s, *t))
+                }
+            },
+        };
+
+        Ok(CameraSettings {
+            image_size: (*width.get_ref(), *height.get_ref()),
+            filepath: desc
+                .output
+                .clone()
+                .unwrap_or_else(|| "output.png".to_string()),
+            max_depth: desc.max_depth.unwrap_or(50),
+            samples_per_pixel,
+            vfov,
+            look_from,
+            look_at,
+            vup,
+            defocus_angle,
+            focus_dist,
+            shutter: (shutter_open, shutter_close),
+            russian_roulette: desc.russian_roulette,
+            projection,
+            stereo,
+            aperture,
+            vignetting,
+            autofocus,
+        })
+    }
+}
+
+// emitters that can be sampled directly, placed ones are only found by
+// BSDF sampling
+fn is_light(object: &ObjectDesc, materials: &BTreeMap<String, MaterialDesc>) -> bool {
+    let emissive = materials
+        .get(object.material.get_ref())
+        .is_some_and(|material| material.kind.get_ref() == "diffuse_light");
+    let samplable = matches!(
+        object.kind.get_ref().as_str(),
+        "sphere" | "triangle" | "rectangle" | "quad"
+    );
+    let placed = object.scale.is_some()
+        || object.rotate.is_some()
+        || object.translate.is_some()
+        || object.motion.is_some()
+        || object.density.is_some();
+    emissive && samplable && !placed
+}
+
+/// Builds a scene from TOML source text. Relative mesh paths are resolved
+/// against the working directory.
+pub fn parse_scene(source: &str) -> Result<Scene, SceneError> {
+    parse_scene_in(source, PathBuf::new())
+}
+
+fn parse_scene_in(source: &str, base_dir: PathBuf) -> Result<Scene, SceneError> {
+    let loader = Loader {
+        source,
+        base_dir,
+        meshes: RefCell::new(HashMap::new()),
+    };
+    let desc: SceneDesc = toml::from_str(source).map_err(|err| match err.span() {
+        Some(span) => loader.error(span, err.message().to_string()),
+        None => SceneError {
+            message: err.message().to_string(),
+            line: None,
+            column: None,
+        },
+    })?;
+
+    let mut textures = BTreeMap::new();
+    for (name, texture) in desc.textures.iter() {
+        textures.insert(name.clone(), loader.texture(name, texture)?);
+    }
+
+    let mut materials = BTreeMap::new();
+    for (name, material) in desc.materials.iter() {
+        materials.insert(name.clone(), loader.material(name, material, &textures)?);
+    }
+
+    let mut objects = HittableList::new();
+    let mut lights = HittableList::new();
+    for (index, object_desc) in desc.objects.iter().enumerate() {
+        let object = loader.object(index, object_desc, &materials)?;
+        if is_light(object_desc, &desc.materials) {
+            lights.add(Box::new(Arc::clone(&object)));
+        }
+        objects.add(Box::new(object));
+    }
+    let mut world = HittableList::new();
+    world.add(Box::new(BvhNode::new(objects)));
+
+    let camera = loader.camera(&desc.camera)?;
+    let environment = match &desc.environment {
+        Some(environment) => Some(loader.environment(environment)?),
+        None => None,
+    };
+    Ok(Scene {
+        world,
+        lights,
+        camera,
+        environment,
+    })
+}
+
+pub fn load_scene<P: AsRef<Path>>(path: P) -> Result<Scene, SceneError> {
+    let source = std::fs::read_to_string(path.as_ref()).map_err(|err| SceneError {
+        message: format!("failed to read {}: {}", path.as_ref().display(), err),
+        line: None,
+        column: None,
+    })?;
+    let base_dir = path
+        .as_ref()
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    parse_scene_in(&source, base_dir)
+}
@@ -0,0 +1,276 @@
+use crate::{
+    aperture::Aperture,
+    bvh::BvhNode,
+    camera::CameraSettings,
+    hit::HittableList,
+    material::materials::{Dielectric, Lambertian, Metal, Mirror},
+    model::{rectangle::Rectangle, sphere::Sphere, triangle::Triangle},
+    projection::Projection,
+    rng::random,
+    scene::Scene,
+    vec3::Vec3,
+};
+
+pub struct BuiltinScene {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub build: fn() -> Scene,
+}
+
+pub const BUILTIN_SCENES: &[BuiltinScene] = &[
+    BuiltinScene {
+        name: "random_spheres",
+        description: "ground plane covered in ~480 small random spheres plus three large ones",
+        build: random_spheres,
+    },
+    BuiltinScene {
+        name: "tetrahedron",
+        description: "a diffuse tetrahedron built from four triangles",
+        build: tetrahedron,
+    },
+    BuiltinScene {
+        name: "mirror_room",
+        description: "metal ball in a corner of mirror walls, stresses deep reflections",
+        build: mirror_room,
+    },
+    BuiltinScene {
+        name: "fov_test",
+        description: "two touching spheres filling a 90 degree field of view",
+        build: fov_test,
+    },
+];
+
+pub fn builtin_scene(name: &str) -> Option<Scene> {
+    BUILTIN_SCENES
+        .iter()
+        .find(|scene| scene.name == name)
+        .map(|scene| (scene.build)())
+}
+
+pub fn random_spheres_world() -> HittableList {
+    let mut world = HittableList::new();
+
+    let material_ground = Lambertian::new(Vec3::new(0.5, 0.5, 0.5));
+    world.add(Box::new(Sphere::new(
+        Vec3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        material_ground.clone(),
+    )));
+    for a in -11..11 {
+        for b in -11..11 {
+            let choose_mat = random::<f64>();
+            let center = Vec3::new(
+                a as f64 + 0.9 * random::<f64>(),
+                0.2,
+                b as f64 + 0.9 * random::<f64>(),
+            );
+
+            if (center - Vec3::new(4.0, 0.2, 0.0)).length() > 0.9 {
+                if choose_mat < 0.8 {
+                    let albedo = Vec3::random_vec() * Vec3::random_vec();
+                    let sphere_material = Lambertian::new(albedo);
+                    world.add(Box::new(Sphere::new(center, 0.2, sphere_material.clone())));
+                } else if choose_mat < 0.95 {
+                    let albedo = Vec3::random(0.5, 1.0);
+                    let fuzz = random::<f64>() * 0.5;
+                    let sphere_material = Metal::new(albedo, fuzz);
+                    world.add(Box::new(Sphere::new(center, 0.2, sphere_material.clone())));
+                } else {
+                    let sphere_material = Dielectric::new(1.5);
+                    world.add(Box::new(Sphere::new(center, 0.2, sphere_material.clone())));
+                }
+            }
+        }
+    }
+
+    let material_left = Dielectric::new(1.5);
+    world.add(Box::new(Sphere::new(
+        Vec3::new(0.0, 1.0, 0.0),
+        1.0,
+        material_left.clone(),
+    )));
+    let material_center = Lambertian::new(Vec3::new(0.4, 0.2, 0.1));
+    world.add(Box::new(Sphere::new(
+        Vec3::new(-4.0, 1.0, 0.0),
+        1.0,
+        material_center.clone(),
+    )));
+    let material_right = Metal::new(Vec3::new(0.7, 0.6, 0.5), 0.0);
+    world.add(Box::new(Sphere::new(
+        Vec3::new(4.0, 1.0, 0.0),
+        1.0,
+        material_right.clone(),
+    )));
+
+    let mut scene = HittableList::new();
+    scene.add(Box::new(BvhNode::new(world)));
+    scene
+}
+
+pub fn random_spheres() -> Scene {
+    Scene {
+        world: random_spheres_world(),
+        lights: HittableList::new(),
+        camera: CameraSettings {
+            image_size: (1920, 1080),
+            filepath: "random_scene_sphere.png".to_string(),
+            max_depth: 50,
+            samples_per_pixel: 100,
+            vfov: 20.0,
+            look_from: Vec3::new(13.0, 2.0, 3.0),
+            look_at: Vec3::new(0.0, 0.0, 0.0),
+            vup: Vec3::new(0.0, 1.0, 0.0),
+            defocus_angle: 0.6,
+            focus_dist: 10.0,
+            shutter: (0.0, 0.0),
+            russian_roulette: None,
+            projection: Projection::Perspective,
+            stereo: None,
+            aperture: Aperture::Circle,
+            vignetting: 0.0,
+            autofocus: None,
+        },
+        environment: None,
+    }
+}
+
+pub fn tetrahedron() -> Scene {
+    let mut world = HittableList::new();
+
+    let material_center = Lambertian::new(Vec3::new(0.1, 0.2, 0.5));
+
+    let l1 = Vec3::new(-1.0, 0.0, -1.0);
+    let l2 = Vec3::new(1.0, 0.0, -1.0);
+    let l3 = Vec3::new(0.0, 0.0, 1.0);
+    let l4 = Vec3::new(0.0, 1.0, 0.0);
+
+    world.add(Box::new(Triangle::new(l1, l2, l3, material_center.clone())));
+    world.add(Box::new(Triangle::new(l1, l2, l4, material_center.clone())));
+    world.add(Box::new(Triangle::new(l1, l3, l4, material_center.clone())));
+    world.add(Box::new(Triangle::new(l2, l3, l4, material_center.clone())));
+
+    Scene {
+        world,
+        lights: HittableList::new(),
+        camera: CameraSettings {
+            image_size: (1920, 1080),
+            filepath: "output.png".to_string(),
+            max_depth: 50,
+            samples_per_pixel: 100,
+            vfov: 90.0,
+            look_from: Vec3::new(-2.0, 2.0, 1.0),
+            look_at: Vec3::new(0.0, 0.0, -1.0),
+            vup: Vec3::new(0.0, 1.0, 0.0),
+            defocus_angle: 0.6,
+            focus_dist: 3.4,
+            shutter: (0.0, 0.0),
+            russian_roulette: None,
+            projection: Projection::Perspective,
+            stereo: None,
+            aperture: Aperture::Circle,
+            vignetting: 0.0,
+            autofocus: None,
+        },
+        environment: None,
+    }
+}
+
+pub fn mirror_room() -> Scene {
+    let wall_material = Mirror::new();
+    let ball_material = Metal::new(Vec3::new(0.8, 0.6, 0.2), 0.0);
+
+    let mut world = HittableList::new();
+
+    world.add(Box::new(Rectangle::new(
+        Vec3::new(-5.5, 5.0, -1.0),
+        Vec3::new(-0.5, -1.0, -6.0),
+        Vec3::new(1.0, 0.0, 1.0),
+        wall_material.clone(),
+    )));
+    world.add(Box::new(Rectangle::new(
+        Vec3::new(0.0, 5.0, -1.0),
+        Vec3::new(6.0, -1.0, -1.0),
+        Vec3::new(-1.0, 0.0, 1.0),
+        wall_material.clone(),
+    )));
+    world.add(Box::new(Rectangle::new(
+        Vec3::new(-6.0, 0.0, -1.0),
+        Vec3::new(6.0, -1.0, -1.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        wall_material.clone(),
+    )));
+
+    world.add(Box::new(Sphere::new(
+        Vec3::new(0.0, -0.5, -1.0),
+        0.5,
+        ball_material.clone(),
+    )));
+
+    Scene {
+        world,
+        lights: HittableList::new(),
+        camera: CameraSettings {
+            image_size: (1920, 1080),
+            filepath: "output-case-1.png".to_string(),
+            max_depth: 5,
+            samples_per_pixel: 100,
+            vfov: 90.0,
+            look_from: Vec3::new(0.0, 1.0, 1.0),
+            look_at: Vec3::new(0.0, -0.5, -1.0),
+            vup: Vec3::new(0.0, 1.0, 0.0),
+            defocus_angle: 10.0,
+            focus_dist: 3.4,
+            shutter: (0.0, 0.0),
+            russian_roulette: None,
+            projection: Projection::Perspective,
+            stereo: None,
+            aperture: Aperture::Circle,
+            vignetting: 0.0,
+            autofocus: None,
+        },
+        environment: None,
+    }
+}
+
+pub fn fov_test() -> Scene {
+    let mut world = HittableList::new();
+    let material_left = Lambertian::new(Vec3::new(0.0, 0.0, 1.0));
+    let material_right = Lambertian::new(Vec3::new(1.0, 0.0, 0.0));
+
+    let r = (std::f64::consts::PI / 4.0).cos();
+    world.add(Box::new(Sphere::new(
+        Vec3::new(-r, 0.0, -1.0),
+        r,
+        material_left.clone(),
+    )));
+    world.add(Box::new(Sphere::new(
+        Vec3::new(r, 0.0, -1.0),
+        r,
+        material_right.clone(),
+    )));
+
+    Scene {
+        world,
+        lights: HittableList::new(),
+        camera: CameraSettings {
+            image_size: (1920, 1080),
+            filepath: "fov-test.png".to_string(),
+            max_depth: 50,
+            samples_per_pixel: 100,
+            vfov: 90.0,
+            look_from: Vec3::new(0.0, 0.0, 0.0),
+            look_at: Vec3::new(0.0, 0.0, -1.0),
+            vup: Vec3::new(0.0, 1.0, 0.0),
+            defocus_angle: 10.0,
+            focus_dist: 3.4,
+            shutter: (0.0, 0.0),
+            russian_roulette: None,
+            projection: Projection::Perspective,
+            stereo: None,
+            aperture: Aperture::Circle,
+            vignetting: 0.0,
+            autofocus: None,
+        },
+        environment: None,
+    }
+}
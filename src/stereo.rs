@@ -0,0 +1,79 @@
+use crate::camera::CameraError;
+
+/// Where the two eye views go in the image, the left eye comes first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StereoLayout {
+    SideBySide,
+    TopBottom,
+}
+
+/// Renders a left and a right eye view into one image.
+///
+/// With the equirectangular projection this gives an omni-directional
+/// stereo (ODS) panorama: the eyes sit on a circle of diameter `ipd` and
+/// their offset turns with the longitude.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stereo {
+    // interpupillary distance in world units
+    pub ipd: f64,
+    // distance at which both views line up, objects there appear at screen
+    // depth. `f64::INFINITY` keeps the views parallel, `None` follows the
+    // camera's focus distance, also after autofocus
+    pub convergence: Option<f64>,
+    pub layout: StereoLayout,
+}
+
+impl Stereo {
+    pub fn new(
+        ipd: f64,
+        convergence: Option<f64>,
+        layout: StereoLayout,
+    ) -> Result<Self, CameraError> {
+        Stereo {
+            ipd,
+            convergence,
+            layout,
+        }
+        .validate()
+    }
+
+    // the rig itself when its distances make sense, also for rigs built as struct literals
+    pub fn validate(self) -> Result<Self, CameraError> {
+        if self.ipd >= 0.0 && self.convergence.is_none_or(|c| c > 0.0) {
+            Ok(self)
+        } else {
+            Err(CameraError::InvalidStereo(self))
+        }
+    }
+
+    // size of one eye's view in an image of `image_size`
+    pub fn eye_size(&self, image_size: (u32, u32)) -> (u32, u32) {
+        let (width, height) = image_size;
+        match self.layout {
+            StereoLayout::SideBySide => ((width / 2).max(1), height),
+            StereoLayout::TopBottom => (width, (height / 2).max(1)),
+        }
+    }
+
+    // Which eye the pixel (col, row) belongs to, -1 for the left and 1 for
+    // the right, and where it lies in that eye's view.
+    pub fn eye(&self, col: u32, row: u32, image_size: (u32, u32)) -> (f64, u32, u32) {
+        let (width, height) = self.eye_size(image_size);
+        match self.layout {
+            StereoLayout::SideBySide if col >= width => (1.0, col - width, row),
+            StereoLayout::TopBottom if row >= height => (1.0, col, row - height),
+            _ => (-1.0, col, row),
+        }
+    }
+
+    // convergence distance for a camera focused at `focus_dist`
+    pub fn convergence(&self, focus_dist: f64) -> f64 {
+        self.convergence.unwrap_or(focus_dist)
+    }
+
+    // Fraction of the eye offset by which a pixel grid at `focus_dist` moves
+    // along with the eye, so that the views meet at the convergence distance.
+    pub fn grid_shift(&self, focus_dist: f64) -> f64 {
+        1.0 - focus_dist / self.convergence(focus_dist)
+    }
+}
@@ -0,0 +1,3 @@
+pub mod perlin;
+pub mod textures;
+pub mod worley;
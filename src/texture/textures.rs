@@ -0,0 +1,299 @@
+use std::{path::Path, sync::Arc};
+
+use image::ImageError;
+
+use crate::{
+    texture::{perlin::Perlin, worley::Worley},
+    vec3::Vec3,
+};
+
+pub trait Texture: Send + Sync {
+    // color at surface coordinates (u, v), `p` is the hit point in world space
+    fn value(&self, u: f64, v: f64, p: &Vec3) -> Vec3;
+}
+
+pub struct SolidColor {
+    pub albedo: Vec3,
+}
+
+impl SolidColor {
+    pub fn new(albedo: Vec3) -> Arc<Self> {
+        Arc::new(SolidColor { albedo })
+    }
+}
+
+impl Texture for SolidColor {
+    fn value(&self, u: f64, v: f64, p: &Vec3) -> Vec3 {
+        let _ = (u, v, p);
+        self.albedo
+    }
+}
+
+/// Checkerboard in texture space, `frequency` squares per unit of u and v.
+pub struct CheckerTexture {
+    pub frequency: f64,
+    pub even: Arc<dyn Texture>,
+    pub odd: Arc<dyn Texture>,
+}
+
+impl CheckerTexture {
+    pub fn new(frequency: f64, even: Arc<dyn Texture>, odd: Arc<dyn Texture>) -> Arc<Self> {
+        Arc::new(CheckerTexture {
+            frequency,
+            even,
+            odd,
+        })
+    }
+
+    pub fn new_colors(frequency: f64, even: Vec3, odd: Vec3) -> Arc<Self> {
+        CheckerTexture::new(frequency, SolidColor::new(even), SolidColor::new(odd))
+    }
+}
+
+impl Texture for CheckerTexture {
+    fn value(&self, u: f64, v: f64, p: &Vec3) -> Vec3 {
+        let cell = (u * self.frequency).floor() as i64 + (v * self.frequency).floor() as i64;
+        if cell.rem_euclid(2) == 0 {
+            self.even.value(u, v, p)
+        } else {
+            self.odd.value(u, v, p)
+        }
+    }
+}
+
+/// Texture read from an image file. Texels are converted from sRGB to
+/// linear and filtered bilinearly; coordinates outside [0, 1] repeat.
+pub struct ImageTexture {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Vec3>,
+}
+
+impl ImageTexture {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Arc<Self>, ImageError> {
+        let image = image::open(path)?.into_rgb8();
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let srgb_to_linear = |c: u8| {
+            let c = c as f64 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let pixels = image
+            .pixels()
+            .map(|p| {
+                Vec3::new(
+                    srgb_to_linear(p[0]),
+                    srgb_to_linear(p[1]),
+                    srgb_to_linear(p[2]),
+                )
+            })
+            .collect();
+        Ok(Arc::new(ImageTexture {
+            width,
+            height,
+            pixels,
+        }))
+    }
+
+    fn texel(&self, x: isize, y: isize) -> Vec3 {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        self.pixels[y * self.width + x]
+    }
+}
+
+impl Texture for ImageTexture {
+    fn value(&self, u: f64, v: f64, p: &Vec3) -> Vec3 {
+        let _ = p;
+        if self.pixels.is_empty() {
+            // solid cyan makes missing textures easy to spot
+            return Vec3::new(0.0, 1.0, 1.0);
+        }
+        // v points up, image rows go down
+        let x = u * self.width as f64 - 0.5;
+        let y = (1.0 - v) * self.height as f64 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as isize, y0 as isize);
+
+        let top = self.texel(x0, y0) * (1.0 - fx) + self.texel(x0 + 1, y0) * fx;
+        let bottom = self.texel(x0, y0 + 1) * (1.0 - fx) + self.texel(x0 + 1, y0 + 1) * fx;
+        top * (1.0 - fy) + bottom * fy
+    }
+}
+
+fn lerp(low: &Vec3, high: &Vec3, t: f64) -> Vec3 {
+    *low * (1.0 - t) + *high * t
+}
+
+/// Plain Perlin noise, evaluated at the hit point scaled by `scale`.
+pub struct NoiseTexture {
+    pub noise: Perlin,
+    pub scale: f64,
+    pub low: Vec3,
+    pub high: Vec3,
+}
+
+impl NoiseTexture {
+    pub fn new(seed: u64, scale: f64, low: Vec3, high: Vec3) -> Arc<Self> {
+        Arc::new(NoiseTexture {
+            noise: Perlin::new(seed),
+            scale,
+            low,
+            high,
+        })
+    }
+}
+
+impl Texture for NoiseTexture {
+    fn value(&self, u: f64, v: f64, p: &Vec3) -> Vec3 {
+        let _ = (u, v);
+        let t = 0.5 * (1.0 + self.noise.noise(&(*p * self.scale)));
+        lerp(&self.low, &self.high, t.clamp(0.0, 1.0))
+    }
+}
+
+/// Multi-octave turbulence, see `Perlin::turbulence`.
+pub struct TurbulenceTexture {
+    pub noise: Perlin,
+    pub scale: f64,
+    pub octaves: u32,
+    pub low: Vec3,
+    pub high: Vec3,
+}
+
+impl TurbulenceTexture {
+    pub fn new(seed: u64, scale: f64, octaves: u32, low: Vec3, high: Vec3) -> Arc<Self> {
+        Arc::new(TurbulenceTexture {
+            noise: Perlin::new(seed),
+            scale,
+            octaves,
+            low,
+            high,
+        })
+    }
+}
+
+impl Texture for TurbulenceTexture {
+    fn value(&self, u: f64, v: f64, p: &Vec3) -> Vec3 {
+        let _ = (u, v);
+        let t = self.noise.turbulence(&(*p * self.scale), self.octaves);
+        lerp(&self.low, &self.high, t.clamp(0.0, 1.0))
+    }
+}
+
+/// Marble veins: stripes along z whose phase is distorted by turbulence.
+pub struct MarbleTexture {
+    pub noise: Perlin,
+    pub scale: f64,
+    pub octaves: u32,
+    // how far the turbulence bends the stripes
+    pub strength: f64,
+    pub low: Vec3,
+    pub high: Vec3,
+}
+
+impl MarbleTexture {
+    pub fn new(
+        seed: u64,
+        scale: f64,
+        octaves: u32,
+        strength: f64,
+        low: Vec3,
+        high: Vec3,
+    ) -> Arc<Self> {
+        Arc::new(MarbleTexture {
+            noise: Perlin::new(seed),
+            scale,
+            octaves,
+            strength,
+            low,
+            high,
+        })
+    }
+}
+
+impl Texture for MarbleTexture {
+    fn value(&self, u: f64, v: f64, p: &Vec3) -> Vec3 {
+        let _ = (u, v);
+        let p = *p * self.scale;
+        let phase = p.z + self.strength * self.noise.turbulence(&p, self.octaves);
+        lerp(&self.low, &self.high, 0.5 * (1.0 + phase.sin()))
+    }
+}
+
+/// Wood grain: rings around the y axis, `rings` per unit of radius,
+/// wobbled by turbulence.
+pub struct WoodTexture {
+    pub noise: Perlin,
+    pub scale: f64,
+    pub rings: f64,
+    pub octaves: u32,
+    pub strength: f64,
+    pub low: Vec3,
+    pub high: Vec3,
+}
+
+impl WoodTexture {
+    pub fn new(
+        seed: u64,
+        scale: f64,
+        octaves: u32,
+        rings: f64,
+        strength: f64,
+        low: Vec3,
+        high: Vec3,
+    ) -> Arc<Self> {
+        Arc::new(WoodTexture {
+            noise: Perlin::new(seed),
+            scale,
+            rings,
+            octaves,
+            strength,
+            low,
+            high,
+        })
+    }
+}
+
+impl Texture for WoodTexture {
+    fn value(&self, u: f64, v: f64, p: &Vec3) -> Vec3 {
+        let _ = (u, v);
+        let p = *p * self.scale;
+        let radius = (p.x * p.x + p.z * p.z).sqrt();
+        let ring = radius * self.rings + self.strength * self.noise.turbulence(&p, self.octaves);
+        // most of each ring stays near `low`, `high` is a narrow band at its end
+        let t = (ring - ring.floor()).powi(3);
+        lerp(&self.low, &self.high, t)
+    }
+}
+
+/// Voronoi cells: `low` along the cell borders fading to `high` inside.
+pub struct WorleyTexture {
+    pub noise: Worley,
+    pub scale: f64,
+    pub low: Vec3,
+    pub high: Vec3,
+}
+
+impl WorleyTexture {
+    pub fn new(seed: u64, scale: f64, low: Vec3, high: Vec3) -> Arc<Self> {
+        Arc::new(WorleyTexture {
+            noise: Worley::new(seed),
+            scale,
+            low,
+            high,
+        })
+    }
+}
+
+impl Texture for WorleyTexture {
+    fn value(&self, u: f64, v: f64, p: &Vec3) -> Vec3 {
+        let _ = (u, v);
+        let (f1, f2) = self.noise.distances(&(*p * self.scale));
+        lerp(&self.low, &self.high, (f2 - f1).clamp(0.0, 1.0))
+    }
+}
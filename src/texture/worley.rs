@@ -0,0 +1,47 @@
+use crate::{rng::mix_seed, vec3::Vec3};
+
+/// Worley (cellular) noise: one feature point is scattered in every unit
+/// cell of space, positions are a hash of the cell and the seed.
+pub struct Worley {
+    seed: u64,
+}
+
+impl Worley {
+    pub fn new(seed: u64) -> Self {
+        Worley { seed }
+    }
+
+    fn feature_point(&self, cell: (i64, i64, i64)) -> Vec3 {
+        let mut hash = mix_seed(self.seed, cell.0 as u64);
+        hash = mix_seed(hash, cell.1 as u64);
+        hash = mix_seed(hash, cell.2 as u64);
+        // three 21 bit fractions out of one hash
+        let fraction = |shift: u32| ((hash >> shift) & 0x1f_ffff) as f64 / (1u64 << 21) as f64;
+        Vec3::new(
+            cell.0 as f64 + fraction(0),
+            cell.1 as f64 + fraction(21),
+            cell.2 as f64 + fraction(42),
+        )
+    }
+
+    /// Distances from `p` to the closest and second closest feature points.
+    pub fn distances(&self, p: &Vec3) -> (f64, f64) {
+        let cell = (p.x.floor() as i64, p.y.floor() as i64, p.z.floor() as i64);
+        let (mut f1, mut f2) = (f64::INFINITY, f64::INFINITY);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let point = self.feature_point((cell.0 + dx, cell.1 + dy, cell.2 + dz));
+                    let distance = (point - *p).length();
+                    if distance < f1 {
+                        f2 = f1;
+                        f1 = distance;
+                    } else if distance < f2 {
+                        f2 = distance;
+                    }
+                }
+            }
+        }
+        (f1, f2)
+    }
+}
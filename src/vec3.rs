@@ -1,5 +1,5 @@
 #![allow(unused)]
-use rand::random;
+use crate::rng::random;
 use std::{
     fmt::{write, Display},
     ops,
@@ -1,5 +1,8 @@
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
+
+use image::{ImageError, ImageFormat, Rgb, Rgb32FImage};
 
 use crate::vec3::Vec3;
 
@@ -20,4 +23,57 @@ impl PPMWriter {
         self.writer.write_all(pixel.write_ppm().as_bytes())?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    // tone mapped 8-bit image, encoded by the image crate (png, jpg, bmp, ...)
+    Ldr,
+    // 32-bit float OpenEXR, linear radiance
+    Exr,
+    // Portable Float Map, linear radiance
+    Pfm,
+}
+
+impl OutputFormat {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("exr") => OutputFormat::Exr,
+            Some("pfm") => OutputFormat::Pfm,
+            _ => OutputFormat::Ldr,
+        }
+    }
+}
+
+pub fn write_exr<P: AsRef<Path>>(path: P, film: &[Vec<Vec3>]) -> Result<(), ImageError> {
+    let height = film.len() as u32;
+    let width = film.first().map_or(0, |row| row.len()) as u32;
+    let image = Rgb32FImage::from_fn(width, height, |col, row| {
+        let pixel = &film[row as usize][col as usize];
+        Rgb([pixel.x as f32, pixel.y as f32, pixel.z as f32])
+    });
+    image.save_with_format(path, ImageFormat::OpenExr)
+}
+
+pub fn write_pfm<P: AsRef<Path>>(path: P, film: &[Vec<Vec3>]) -> Result<(), std::io::Error> {
+    let height = film.len();
+    let width = film.first().map_or(0, |row| row.len());
+    let file = File::create(path)?;
+    let mut writer = std::io::BufWriter::new(file);
+    // negative scale marks little endian data
+    writer.write_all(format!("PF\n{} {}\n-1.0\n", width, height).as_bytes())?;
+    // scanlines are stored bottom to top
+    for row in film.iter().rev() {
+        for pixel in row.iter() {
+            for channel in [pixel.x, pixel.y, pixel.z] {
+                writer.write_all(&(channel as f32).to_le_bytes())?;
+            }
+        }
+    }
+    writer.flush()
+}
@@ -0,0 +1,90 @@
+use image::{GrayImage, Luma};
+use ray_trace::{
+    aperture::{vignetted, Aperture, ApertureMask},
+    rng,
+    vec3::Vec3,
+};
+
+fn write_mask(name: &str, texels: [u8; 4]) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("ray_trace_{}_{}.png", name, std::process::id()));
+    let mut image = GrayImage::new(2, 2);
+    for (i, value) in texels.into_iter().enumerate() {
+        image.put_pixel(i as u32 % 2, i as u32 / 2, Luma([value]));
+    }
+    image.save(&path).expect("write the mask");
+    path
+}
+
+#[test]
+fn circle_samples_stay_in_the_unit_disk() {
+    rng::seed(23);
+    for _ in 0..10000 {
+        let p = Aperture::Circle.sample();
+        assert!(p.length_squared() <= 1.0 && p.z == 0.0, "{}", p);
+    }
+}
+
+#[test]
+fn polygon_samples_stay_in_the_polygon() {
+    rng::seed(23);
+    let aperture = Aperture::Polygon {
+        blades: 6,
+        rotation: 10.0,
+    };
+    let corners: Vec<Vec3> = (0..6)
+        .map(|i| {
+            let angle = (10.0 + 60.0 * i as f64).to_radians();
+            Vec3::new(angle.cos(), angle.sin(), 0.0)
+        })
+        .collect();
+    let mut farthest: f64 = 0.0;
+    for _ in 0..10000 {
+        let p = aperture.sample();
+        // on the inner side of every edge, corners run counterclockwise
+        for i in 0..6 {
+            let (a, b) = (corners[i], corners[(i + 1) % 6]);
+            let edge = b - a;
+            let side = edge.x * (p.y - a.y) - edge.y * (p.x - a.x);
+            assert!(side >= -1e-12, "{} outside edge {}", p, i);
+        }
+        farthest = farthest.max(p.length());
+    }
+    // and it reaches out towards the corners
+    assert!(farthest > 0.95, "{}", farthest);
+}
+
+#[test]
+fn mask_samples_follow_the_bright_texels() {
+    rng::seed(23);
+    // only the top right texel lets light through
+    let path = write_mask("aperture_mask", [0, 255, 0, 0]);
+    let mask = ApertureMask::load(&path).expect("load the mask");
+    std::fs::remove_file(&path).ok();
+    let aperture = Aperture::Mask(mask);
+    let mut farthest: f64 = 0.0;
+    for _ in 0..1000 {
+        let p = aperture.sample();
+        assert!(p.x >= 0.0 && p.y >= 0.0 && p.z == 0.0, "{}", p);
+        assert!(p.length_squared() <= 1.0, "{} outside the lens", p);
+        farthest = farthest.max(p.length());
+    }
+    // the texel reaches out to the rim of the lens
+    assert!(farthest > 0.95, "{}", farthest);
+
+    // a black mask is a pinhole
+    let path = write_mask("aperture_black", [0; 4]);
+    let mask = ApertureMask::load(&path).expect("load the mask");
+    std::fs::remove_file(&path).ok();
+    assert_eq!(Aperture::Mask(mask).sample().length(), 0.0);
+}
+
+#[test]
+fn vignetting_cuts_the_lens_towards_the_corners() {
+    let lens = Vec3::new(-0.9, 0.0, 0.0);
+    // the center of the frame is never vignetted
+    assert!(!vignetted(&lens, &Vec3::new_zero(), 1.0));
+    // in the right corner the barrel moves right and blocks the left of the lens
+    assert!(vignetted(&lens, &Vec3::new(1.0, 0.0, 0.0), 0.5));
+    assert!(!vignetted(&(lens * -1.0), &Vec3::new(1.0, 0.0, 0.0), 0.5));
+    assert!(!vignetted(&lens, &Vec3::new(1.0, 0.0, 0.0), 0.0));
+}
@@ -0,0 +1,80 @@
+use ray_trace::{
+    camera::CameraBuilder, hit::HittableList, material::materials::Lambertian,
+    model::sphere::Sphere, vec3::Vec3,
+};
+
+// a ball straight ahead whose front is 2.5 units from the camera
+fn world() -> HittableList {
+    let mut world = HittableList::new();
+    world.add(Box::new(Sphere::new(
+        Vec3::new(0.0, 0.0, -3.0),
+        0.5,
+        Lambertian::new(Vec3::new(0.5, 0.5, 0.5)),
+    )));
+    world
+}
+
+#[test]
+fn autofocus_finds_the_surface_at_the_point() {
+    let camera = CameraBuilder::new()
+        .image_size(32, 32)
+        .aperture(2.0)
+        .autofocus((0.5, 0.5))
+        .build()
+        .expect("valid camera");
+    let focus_dist = camera.autofocus(&world());
+    assert!((focus_dist - 2.5).abs() < 1e-9, "{}", focus_dist);
+    let config = camera.focused_config(&world());
+    assert!((config.focus_dist - 2.5).abs() < 1e-9);
+    // the defocus disk keeps the 2 degree cone to the new focus plane
+    let radius = config.defocus_u.length();
+    assert!(
+        (radius - 2.5 * 1f64.to_radians().tan()).abs() < 1e-9,
+        "{}",
+        radius
+    );
+}
+
+#[test]
+fn autofocus_falls_back_when_nothing_is_there() {
+    // the top left corner looks past the ball
+    let mut camera = CameraBuilder::new()
+        .image_size(32, 32)
+        .focus_distance(4.0)
+        .autofocus((0.0, 0.0))
+        .build()
+        .expect("valid camera");
+    let focus_dist = camera.autofocus(&world());
+    assert!((focus_dist - 4.0).abs() < 1e-9, "{}", focus_dist);
+
+    // the fallback is the focus distance at the time of the render
+    camera.set_focus_dist(6.0).expect("valid focus distance");
+    assert!((camera.autofocus(&world()) - 6.0).abs() < 1e-9);
+}
+
+#[test]
+fn autofocus_leaves_the_camera_focus_alone() {
+    let mut camera = CameraBuilder::new()
+        .image_size(4, 4)
+        .samples(1)
+        .focus_distance(4.0)
+        .autofocus((0.5, 0.5))
+        .build()
+        .expect("valid camera");
+    camera.disable_progress_bar();
+    let world = world();
+    camera.render(&world, false);
+    assert!((camera.config.focus_dist - 4.0).abs() < 1e-9);
+
+    // a second render focuses again from the same start
+    camera.render(&world, false);
+    assert!((camera.config.focus_dist - 4.0).abs() < 1e-9);
+    assert!((camera.autofocus(&world) - 2.5).abs() < 1e-9);
+}
+
+#[test]
+fn autofocus_points_outside_the_image_are_rejected() {
+    for point in [(-0.1, 0.5), (0.5, 1.1), (f64::NAN, 0.5)] {
+        assert!(CameraBuilder::new().autofocus(point).build().is_err());
+    }
+}
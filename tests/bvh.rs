@@ -0,0 +1,113 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use ray_trace::{
+    bvh::BvhNode,
+    hit::{Hittable, HittableList},
+    interval::Interval,
+    material::materials::Lambertian,
+    model::{sphere::Sphere, triangle::Triangle},
+    ray::Ray,
+    vec3::Vec3,
+};
+
+fn random_point(rng: &mut StdRng, scale: f64) -> Vec3 {
+    Vec3::new(
+        rng.gen::<f64>() - 0.5,
+        rng.gen::<f64>() - 0.5,
+        rng.gen::<f64>() - 0.5,
+    ) * scale
+}
+
+// spheres and triangles scattered through a 20 unit cube, some overlapping,
+// the same objects for the same seed
+fn random_objects(seed: u64, count: usize) -> HittableList {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let material = Lambertian::new(Vec3::new(0.5, 0.5, 0.5));
+    let mut list = HittableList::new();
+    for i in 0..count {
+        let center = random_point(&mut rng, 20.0);
+        if i % 2 == 0 {
+            let radius = 0.2 + rng.gen::<f64>();
+            list.add(Box::new(Sphere::new(center, radius, material.clone())));
+        } else {
+            let b = center + random_point(&mut rng, 3.0);
+            let c = center + random_point(&mut rng, 3.0);
+            list.add(Box::new(Triangle::new(center, b, c, material.clone())));
+        }
+    }
+    list
+}
+
+fn random_ray(rng: &mut StdRng) -> Ray {
+    let origin = random_point(rng, 30.0);
+    Ray::new(origin, random_point(rng, 20.0) - origin)
+}
+
+#[test]
+fn bvh_hits_match_the_list() {
+    let list = random_objects(5, 300);
+    let bvh = BvhNode::new(random_objects(5, 300));
+
+    let mut rng = StdRng::seed_from_u64(1);
+    let interval = Interval::new(0.001, f64::INFINITY);
+    let mut hits = 0;
+    for _ in 0..2000 {
+        let ray = random_ray(&mut rng);
+        match (list.hit(&ray, &interval), bvh.hit(&ray, &interval)) {
+            (None, None) => {}
+            (Some(expected), Some(found)) => {
+                hits += 1;
+                assert!(
+                    (expected.t - found.t).abs() < 1e-9,
+                    "{} != {}",
+                    expected.t,
+                    found.t
+                );
+                assert!((expected.p - found.p).length() < 1e-9);
+            }
+            (expected, found) => {
+                panic!(
+                    "list hit {}, bvh hit {}",
+                    expected.is_some(),
+                    found.is_some()
+                )
+            }
+        }
+    }
+    // a good share of the rays go through the cloud of objects
+    assert!(hits > 500, "only {} hits", hits);
+}
+
+#[test]
+fn bvh_respects_the_interval() {
+    let list = random_objects(6, 60);
+    let bvh = BvhNode::new(random_objects(6, 60));
+
+    let mut rng = StdRng::seed_from_u64(2);
+    for _ in 0..500 {
+        let ray = random_ray(&mut rng);
+        let interval = Interval::new(0.2 + rng.gen::<f64>(), 0.6 + rng.gen::<f64>());
+        let expected = list.hit(&ray, &interval).map(|hit| hit.t);
+        let found = bvh.hit(&ray, &interval).map(|hit| hit.t);
+        assert_eq!(expected.is_some(), found.is_some());
+        if let (Some(expected), Some(found)) = (expected, found) {
+            assert!((expected - found).abs() < 1e-9);
+            assert!(interval.contains(found));
+        }
+    }
+}
+
+#[test]
+fn bvh_box_encloses_its_objects() {
+    let list = random_objects(7, 90);
+    let boxes: Vec<_> = list
+        .objects
+        .iter()
+        .map(|object| object.bounding_box())
+        .collect();
+    let bbox = BvhNode::new(list).bounding_box();
+    for inner in boxes {
+        assert!(bbox.x.min <= inner.x.min && inner.x.max <= bbox.x.max);
+        assert!(bbox.y.min <= inner.y.min && inner.y.max <= bbox.y.max);
+        assert!(bbox.z.min <= inner.z.min && inner.z.max <= bbox.z.max);
+    }
+}
@@ -0,0 +1,241 @@
+use ray_trace::{
+    aperture::Aperture,
+    camera::{Camera, CameraBuilder, CameraError},
+    projection::Projection,
+    stereo::{Stereo, StereoLayout},
+    vec3::Vec3,
+};
+
+fn build_error(builder: CameraBuilder) -> CameraError {
+    builder
+        .build()
+        .err()
+        .expect("the camera should be rejected")
+}
+
+#[test]
+fn defaults_build() {
+    let camera = CameraBuilder::new().build().expect("valid camera");
+    assert_eq!(camera.config.image_size, (400, 225));
+    assert!((camera.config.focus_dist - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn rejects_empty_images_and_samples() {
+    let error = build_error(CameraBuilder::new().image_size(0, 10));
+    assert!(
+        matches!(error, CameraError::ZeroImageSize((0, 10))),
+        "{:?}",
+        error
+    );
+    let error = build_error(CameraBuilder::new().samples(0));
+    assert!(matches!(error, CameraError::ZeroSamples), "{:?}", error);
+}
+
+#[test]
+fn rejects_degenerate_views() {
+    let error = build_error(CameraBuilder::new().look_at(Vec3::new_zero()));
+    assert!(
+        matches!(error, CameraError::LookFromIsLookAt(_)),
+        "{:?}",
+        error
+    );
+    let error = build_error(CameraBuilder::new().vup(Vec3::new(0.0, 0.0, 2.0)));
+    assert!(
+        matches!(error, CameraError::VupParallelToView(_)),
+        "{:?}",
+        error
+    );
+    let error = build_error(CameraBuilder::new().vfov(180.0));
+    assert!(
+        matches!(error, CameraError::InvalidFieldOfView(_)),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn rejects_invalid_lenses() {
+    let error = build_error(CameraBuilder::new().aperture(-1.0));
+    assert!(
+        matches!(error, CameraError::InvalidAperture(_)),
+        "{:?}",
+        error
+    );
+    let error = build_error(CameraBuilder::new().focus_distance(0.0));
+    assert!(
+        matches!(error, CameraError::NonPositiveFocusDistance(_)),
+        "{:?}",
+        error
+    );
+    let error = build_error(CameraBuilder::new().vignetting(-0.5));
+    assert!(
+        matches!(error, CameraError::InvalidVignetting(_)),
+        "{:?}",
+        error
+    );
+    let error = build_error(CameraBuilder::new().autofocus((0.5, 1.5)));
+    assert!(
+        matches!(error, CameraError::InvalidAutofocus(_)),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn rejects_invalid_shutter_and_projection() {
+    let error = build_error(CameraBuilder::new().shutter(1.0, 0.0));
+    assert!(
+        matches!(error, CameraError::InvalidShutter((1.0, 0.0))),
+        "{:?}",
+        error
+    );
+    let error =
+        build_error(CameraBuilder::new().projection(Projection::Orthographic { height: 0.0 }));
+    assert!(
+        matches!(error, CameraError::InvalidProjection(_)),
+        "{:?}",
+        error
+    );
+    let error = build_error(CameraBuilder::new().projection(Projection::Fisheye { fov: 400.0 }));
+    assert!(
+        matches!(error, CameraError::InvalidProjection(_)),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn rejects_invalid_stereo() {
+    let error = Stereo::new(-0.1, None, StereoLayout::SideBySide).expect_err("negative ipd");
+    assert!(
+        matches!(error, CameraError::InvalidStereo(_)),
+        "{:?}",
+        error
+    );
+    let error =
+        Stereo::new(0.064, Some(0.0), StereoLayout::TopBottom).expect_err("zero convergence");
+    assert!(
+        matches!(error, CameraError::InvalidStereo(_)),
+        "{:?}",
+        error
+    );
+
+    // built without `Stereo::new`, the builder still checks it
+    let stereo = Stereo {
+        ipd: 0.064,
+        convergence: Some(-1.0),
+        layout: StereoLayout::SideBySide,
+    };
+    let error = build_error(CameraBuilder::new().stereo(stereo));
+    assert!(
+        matches!(error, CameraError::InvalidStereo(_)),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn setters_reject_what_the_builder_rejects() {
+    let mut camera = CameraBuilder::new().build().expect("valid camera");
+    assert!(matches!(
+        camera.set_shutter(2.0, 1.0),
+        Err(CameraError::InvalidShutter(_))
+    ));
+    assert!(matches!(
+        camera.set_vignetting(f64::NAN),
+        Err(CameraError::InvalidVignetting(_))
+    ));
+    assert!(matches!(
+        camera.set_focus_dist(-2.0),
+        Err(CameraError::NonPositiveFocusDistance(_))
+    ));
+    assert!(matches!(
+        camera.set_projection(Projection::Fisheye { fov: 0.0 }),
+        Err(CameraError::InvalidProjection(_))
+    ));
+    let stereo = Stereo {
+        ipd: -1.0,
+        convergence: None,
+        layout: StereoLayout::SideBySide,
+    };
+    assert!(matches!(
+        camera.set_stereo(stereo),
+        Err(CameraError::InvalidStereo(_))
+    ));
+    let blades = Aperture::Polygon {
+        blades: 2,
+        rotation: 0.0,
+    };
+    assert!(matches!(
+        camera.set_aperture(blades),
+        Err(CameraError::InvalidApertureShape(_))
+    ));
+    let rotation = Aperture::Polygon {
+        blades: 5,
+        rotation: f64::NAN,
+    };
+    assert!(matches!(
+        camera.set_aperture(rotation),
+        Err(CameraError::InvalidApertureShape(_))
+    ));
+    // the default camera stops paths after 50 bounces
+    assert!(matches!(
+        camera.set_russian_roulette(50),
+        Err(CameraError::InvalidRussianRoulette((50, 50)))
+    ));
+    assert!(matches!(
+        camera.set_autofocus((1.5, 0.5)),
+        Err(CameraError::InvalidAutofocus(_))
+    ));
+    // rejected values leave the camera as it was
+    assert_eq!(camera.config.shutter, (0.0, 0.0));
+    assert!((camera.config.focus_dist - 1.0).abs() < 1e-9);
+    assert_eq!(camera.config.projection, Projection::Perspective);
+    assert!(camera.config.stereo.is_none());
+    assert!(matches!(camera.config.aperture, Aperture::Circle));
+    assert!(camera.config.russian_roulette.is_none());
+    assert!(camera.config.autofocus.is_none());
+}
+
+#[test]
+fn new_rejects_what_the_builder_rejects() {
+    let view = (
+        Vec3::new_zero(),
+        Vec3::new(0.0, 0.0, -1.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+    let error = Camera::new((0, 9), "unused.png", 10, 1, 90.0, view, (0.0, 1.0))
+        .err()
+        .expect("empty image");
+    assert!(
+        matches!(error, CameraError::ZeroImageSize((0, 9))),
+        "{:?}",
+        error
+    );
+    let error = Camera::new((16, 9), "unused.png", 10, 1, 90.0, view, (0.0, -1.0))
+        .err()
+        .expect("negative focus distance");
+    assert!(
+        matches!(error, CameraError::NonPositiveFocusDistance(_)),
+        "{:?}",
+        error
+    );
+    let (from, _, up) = view;
+    let error = Camera::new(
+        (16, 9),
+        "unused.png",
+        10,
+        1,
+        90.0,
+        (from, from, up),
+        (0.0, 1.0),
+    )
+    .err()
+    .expect("no view direction");
+    assert!(
+        matches!(error, CameraError::LookFromIsLookAt(_)),
+        "{:?}",
+        error
+    );
+}
@@ -0,0 +1,90 @@
+use ray_trace::{
+    camera::Camera,
+    environment::SolidBackground,
+    hit::HittableList,
+    integrator::{Albedo, Integrator, Normals, PathTracer},
+    material::materials::{DiffuseLight, Lambertian},
+    model::sphere::Sphere,
+    ray::Ray,
+    vec3::Vec3,
+};
+
+fn assert_close(a: Vec3, b: Vec3) {
+    assert!((a - b).length() < 1e-9, "{} != {}", a, b);
+}
+
+// a gray ball straight ahead and a light to the right, over a plain background
+fn setup() -> (HittableList, Camera) {
+    let mut world = HittableList::new();
+    world.add(Box::new(Sphere::new(
+        Vec3::new(0.0, 0.0, -2.0),
+        0.5,
+        Lambertian::new(Vec3::new(0.2, 0.4, 0.6)),
+    )));
+    world.add(Box::new(Sphere::new(
+        Vec3::new(3.0, 0.0, -2.0),
+        0.5,
+        DiffuseLight::new(Vec3::new(3.0, 2.0, 1.0)),
+    )));
+    let mut camera = Camera::new(
+        (8, 8),
+        "unused.png",
+        10,
+        1,
+        90.0,
+        (
+            Vec3::new_zero(),
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        ),
+        (0.0, 1.0),
+    )
+    .expect("valid camera");
+    camera.set_environment(SolidBackground::new(Vec3::new(0.1, 0.2, 0.3)));
+    (world, camera)
+}
+
+fn towards(x: f64, y: f64, z: f64) -> Ray {
+    Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(x, y, z))
+}
+
+#[test]
+fn normals_map_the_first_hit_normal() {
+    let (world, camera) = setup();
+    let normals = Normals::new();
+    let config = &camera.config;
+    let front = normals.radiance(towards(0.0, 0.0, -1.0), &world, config);
+    assert_close(front, Vec3::new(0.5, 0.5, 1.0));
+    // the top of the ball faces up
+    let top = Ray::new(Vec3::new(0.0, 2.0, -2.0), Vec3::new(0.0, -1.0, 0.0));
+    assert_close(
+        normals.radiance(top, &world, config),
+        Vec3::new(0.5, 1.0, 0.5),
+    );
+    let miss = normals.radiance(towards(0.0, 1.0, 0.0), &world, config);
+    assert_close(miss, Vec3::new(0.1, 0.2, 0.3));
+}
+
+#[test]
+fn albedo_shows_surface_colors_and_emission() {
+    let (world, camera) = setup();
+    let albedo = Albedo::new();
+    let config = &camera.config;
+    let ball = albedo.radiance(towards(0.0, 0.0, -1.0), &world, config);
+    assert_close(ball, Vec3::new(0.2, 0.4, 0.6));
+    let light = albedo.radiance(towards(3.0, 0.0, -2.0), &world, config);
+    assert_close(light, Vec3::new(3.0, 2.0, 1.0));
+    let miss = albedo.radiance(towards(0.0, -1.0, 0.0), &world, config);
+    assert_close(miss, Vec3::new(0.1, 0.2, 0.3));
+}
+
+#[test]
+fn path_tracer_sees_emission_and_background() {
+    let (world, camera) = setup();
+    let path = PathTracer::new();
+    let config = &camera.config;
+    let light = path.radiance(towards(3.0, 0.0, -2.0), &world, config);
+    assert_close(light, Vec3::new(3.0, 2.0, 1.0));
+    let miss = path.radiance(towards(0.0, 1.0, 0.0), &world, config);
+    assert_close(miss, Vec3::new(0.1, 0.2, 0.3));
+}
@@ -0,0 +1,103 @@
+use std::f64::consts::PI;
+
+use ray_trace::{
+    hit::Hittable,
+    interval::Interval,
+    material::materials::Lambertian,
+    model::{quad::Quad, rectangle::Rectangle, sphere::Sphere, triangle::Triangle},
+    ray::Ray,
+    rng,
+    vec3::Vec3,
+};
+
+const SAMPLES: usize = 200_000;
+
+fn gray() -> std::sync::Arc<Lambertian> {
+    Lambertian::new(Vec3::new(0.5, 0.5, 0.5))
+}
+
+// Monte Carlo estimate of the integral of `pdf_value` over all directions
+// seen from `origin`, uniform directions on the unit sphere
+fn integrate_pdf(object: &dyn Hittable, origin: Vec3) -> f64 {
+    rng::seed(17);
+    let sum: f64 = (0..SAMPLES)
+        .map(|_| object.pdf_value(&origin, &Vec3::random_unit_vector()))
+        .sum();
+    sum / SAMPLES as f64 * 4.0 * PI
+}
+
+// every sampled direction has to hit the object, or its pdf misses weight
+fn assert_samples_hit(object: &dyn Hittable, origin: Vec3) {
+    rng::seed(3);
+    for _ in 0..1000 {
+        let direction = object.random_direction(&origin);
+        assert!(object.pdf_value(&origin, &direction) > 0.0);
+    }
+}
+
+fn assert_integrates_to_one(object: &dyn Hittable, origin: Vec3) {
+    assert_samples_hit(object, origin);
+    let integral = integrate_pdf(object, origin);
+    assert!((integral - 1.0).abs() < 0.05, "pdf integrates to {}", integral);
+}
+
+#[test]
+fn triangle_pdf_integrates_to_one() {
+    let triangle = Triangle::new(
+        Vec3::new(-1.0, 0.0, -1.0),
+        Vec3::new(1.0, 0.0, -1.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        gray(),
+    );
+    // from either side of the triangle
+    assert_integrates_to_one(&triangle, Vec3::new(0.2, 1.0, 0.3));
+    assert_integrates_to_one(&triangle, Vec3::new(-0.3, -0.8, 0.1));
+}
+
+#[test]
+fn quad_pdf_integrates_to_one() {
+    let quad = Quad::new(
+        Vec3::new(-1.0, 2.0, -1.0),
+        Vec3::new(2.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 2.0),
+        gray(),
+    );
+    assert_integrates_to_one(&quad, Vec3::new(0.3, 0.5, -0.2));
+}
+
+#[test]
+fn rectangle_pdf_integrates_to_one() {
+    // corners differ on all three axes, z follows from the tilted plane
+    let rectangle = Rectangle::new(
+        Vec3::new(-1.0, 1.0, -2.5),
+        Vec3::new(1.0, -1.0, -3.5),
+        Vec3::new(0.2, 0.0, 1.0),
+        gray(),
+    );
+    assert_integrates_to_one(&rectangle, Vec3::new(0.3, 0.2, 0.0));
+}
+
+#[test]
+fn sphere_pdf_integrates_to_one() {
+    let sphere = Sphere::new(Vec3::new(0.0, 0.0, -3.0), 1.0, gray());
+    assert_integrates_to_one(&sphere, Vec3::new(0.5, 0.2, 0.0));
+}
+
+#[test]
+fn triangle_normal_faces_the_ray() {
+    let triangle = Triangle::new(
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(4.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, -4.0),
+        gray(),
+    );
+    let forward = Interval::new(0.001, f64::INFINITY);
+    let from_above = Ray::new(Vec3::new(1.0, 1.0, -1.0), Vec3::new(0.0, -1.0, 0.0));
+    let from_below = Ray::new(Vec3::new(1.0, -1.0, -1.0), Vec3::new(0.0, 1.0, 0.0));
+
+    let above = triangle.hit(&from_above, &forward).expect("hit from above");
+    let below = triangle.hit(&from_below, &forward).expect("hit from below");
+    assert!((above.normal - Vec3::new(0.0, 1.0, 0.0)).length() < 1e-9);
+    assert!((below.normal - Vec3::new(0.0, -1.0, 0.0)).length() < 1e-9);
+    assert_ne!(above.front_face, below.front_face);
+}
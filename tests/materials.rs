@@ -0,0 +1,62 @@
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+use ray_trace::{
+    hit::HitRecord,
+    material::materials::{DiffuseLight, Isotropic, Lambertian, Material, Metal},
+    ray::Ray,
+    vec3::Vec3,
+};
+
+fn hit_with(material: std::sync::Arc<dyn Material>) -> (Ray, HitRecord) {
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+    let mut hit = HitRecord::new(Vec3::new_zero(), Vec3::new_zero(), 1.0, material);
+    hit.set_face_normal(&ray, &Vec3::new(0.0, 0.0, 1.0));
+    (ray, hit)
+}
+
+#[test]
+fn lights_emit_and_do_not_scatter() {
+    let light = DiffuseLight::new(Vec3::new(4.0, 3.0, 2.0));
+    let (ray, hit) = hit_with(light.clone());
+    let emitted = light.emitted(&hit);
+    assert_eq!((emitted.x, emitted.y, emitted.z), (4.0, 3.0, 2.0));
+    assert!(light.scatter(&ray, &hit).is_none());
+}
+
+#[test]
+fn other_materials_emit_nothing() {
+    let materials: [std::sync::Arc<dyn Material>; 2] = [
+        Lambertian::new(Vec3::new(0.5, 0.5, 0.5)),
+        Metal::new(Vec3::new(0.8, 0.8, 0.8), 0.0),
+    ];
+    for material in materials {
+        let (ray, hit) = hit_with(material.clone());
+        assert_eq!(material.emitted(&hit).length(), 0.0);
+        assert!(material.scatter(&ray, &hit).is_some());
+    }
+}
+
+#[test]
+fn scattering_pdfs_match_the_sampled_directions() {
+    let lambertian = Lambertian::new(Vec3::new(0.5, 0.5, 0.5));
+    let (ray, hit) = hit_with(lambertian.clone());
+    let along = |direction: Vec3| Ray::new(Vec3::new_zero(), direction);
+    let normal = lambertian.scattering_pdf(&ray, &hit, &along(Vec3::new(0.0, 0.0, 2.0)));
+    assert!((normal - 1.0 / PI).abs() < 1e-12);
+    let slanted = lambertian.scattering_pdf(&ray, &hit, &along(Vec3::new(1.0, 0.0, 1.0)));
+    assert!((slanted - FRAC_1_SQRT_2 / PI).abs() < 1e-12);
+    // nothing scatters below the surface
+    let below = lambertian.scattering_pdf(&ray, &hit, &along(Vec3::new(0.0, 1.0, -1.0)));
+    assert_eq!(below, 0.0);
+
+    let isotropic = Isotropic::new(Vec3::new(0.5, 0.5, 0.5));
+    let (ray, hit) = hit_with(isotropic.clone());
+    let back = isotropic.scattering_pdf(&ray, &hit, &along(Vec3::new(0.0, 0.0, -1.0)));
+    assert!((back - 1.0 / (4.0 * PI)).abs() < 1e-12);
+
+    // specular materials mark themselves with zero
+    let metal = Metal::new(Vec3::new(0.8, 0.8, 0.8), 0.0);
+    let (ray, hit) = hit_with(metal.clone());
+    let (_, reflected) = metal.scatter(&ray, &hit).expect("reflects");
+    assert_eq!(metal.scattering_pdf(&ray, &hit, &reflected), 0.0);
+}
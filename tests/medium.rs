@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use ray_trace::{
+    hit::Hittable,
+    interval::Interval,
+    material::materials::Lambertian,
+    model::{constant_medium::ConstantMedium, sphere::Sphere},
+    ray::Ray,
+    rng,
+    vec3::Vec3,
+};
+
+fn fog(density: f64) -> ConstantMedium {
+    let boundary = Sphere::new(
+        Vec3::new_zero(),
+        1.0,
+        Lambertian::new(Vec3::new(0.5, 0.5, 0.5)),
+    );
+    ConstantMedium::new(Arc::new(boundary), density, Vec3::new(0.8, 0.8, 0.8))
+}
+
+// share of `trials` rays that scatter inside the medium
+fn hit_share(medium: &ConstantMedium, ray: &Ray, interval: &Interval, trials: usize) -> f64 {
+    let mut hits = 0;
+    for _ in 0..trials {
+        if let Some(hit) = medium.hit(ray, interval) {
+            assert!(hit.p.length() <= 1.0 + 1e-9, "{}", hit.p);
+            assert!(interval.contains(hit.t), "{}", hit.t);
+            hits += 1;
+        }
+    }
+    hits as f64 / trials as f64
+}
+
+#[test]
+fn hit_probability_follows_the_path_length() {
+    rng::seed(16);
+    let medium = fog(0.5);
+    let forward = Interval::new(0.001, f64::INFINITY);
+
+    // through the center the ray crosses 2 units of fog
+    let through = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+    let share = hit_share(&medium, &through, &forward, 20000);
+    assert!((share - (1.0 - (-1.0f64).exp())).abs() < 0.02, "{}", share);
+
+    // the direction length does not change the distances
+    let scaled = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -4.0));
+    let share = hit_share(&medium, &scaled, &forward, 20000);
+    assert!((share - (1.0 - (-1.0f64).exp())).abs() < 0.02, "{}", share);
+
+    // starting at the center only 1 unit is left
+    let inside = Ray::new(Vec3::new_zero(), Vec3::new(0.0, 1.0, 0.0));
+    let share = hit_share(&medium, &inside, &forward, 20000);
+    assert!((share - (1.0 - (-0.5f64).exp())).abs() < 0.02, "{}", share);
+}
+
+#[test]
+fn hits_stay_within_the_interval() {
+    rng::seed(17);
+    let medium = fog(2.0);
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+    // only half a unit of the fog lies in the interval
+    let interval = Interval::new(4.0, 4.5);
+    let share = hit_share(&medium, &ray, &interval, 20000);
+    assert!((share - (1.0 - (-1.0f64).exp())).abs() < 0.02, "{}", share);
+
+    // missing the boundary never scatters
+    let miss = Ray::new(Vec3::new(2.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+    assert!(medium
+        .hit(&miss, &Interval::new(0.001, f64::INFINITY))
+        .is_none());
+}
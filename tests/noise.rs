@@ -0,0 +1,78 @@
+use ray_trace::{
+    texture::{
+        perlin::Perlin,
+        textures::{MarbleTexture, Texture, WorleyTexture},
+        worley::Worley,
+    },
+    vec3::Vec3,
+};
+
+fn sample_points() -> Vec<Vec3> {
+    (0..200)
+        .map(|i| {
+            let i = i as f64;
+            Vec3::new(i * 0.173 - 7.0, (i * 0.61).sin() * 5.0, i * 0.037)
+        })
+        .collect()
+}
+
+#[test]
+fn perlin_is_deterministic_per_seed() {
+    let (a, b, other) = (Perlin::new(3), Perlin::new(3), Perlin::new(4));
+    let points = sample_points();
+    for p in points.iter() {
+        assert_eq!(a.noise(p), b.noise(p));
+        assert_eq!(a.turbulence(p, 5), b.turbulence(p, 5));
+        assert!(a.noise(p).abs() <= 1.5, "{}", a.noise(p));
+        assert!(a.turbulence(p, 5) >= 0.0);
+    }
+    assert!(points.iter().any(|p| a.noise(p) != other.noise(p)));
+}
+
+#[test]
+fn perlin_is_zero_on_the_lattice() {
+    let noise = Perlin::new(9);
+    for p in [Vec3::new(0.0, 0.0, 0.0), Vec3::new(3.0, -2.0, 7.0)] {
+        assert!(noise.noise(&p).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn worley_is_deterministic_per_seed() {
+    let (a, b, other) = (Worley::new(3), Worley::new(3), Worley::new(4));
+    let points = sample_points();
+    for p in points.iter() {
+        let (f1, f2) = a.distances(p);
+        assert_eq!((f1, f2), b.distances(p));
+        // the closest feature point is never further than a cell diagonal
+        assert!(0.0 <= f1 && f1 <= f2 && f1 <= 3f64.sqrt(), "{} {}", f1, f2);
+    }
+    assert!(points.iter().any(|p| a.distances(p) != other.distances(p)));
+}
+
+fn components(v: Vec3) -> (f64, f64, f64) {
+    (v.x, v.y, v.z)
+}
+
+#[test]
+fn noise_textures_are_deterministic_per_seed() {
+    let (low, high) = (Vec3::new(0.1, 0.1, 0.1), Vec3::new(0.9, 0.8, 0.7));
+    let marble = (
+        MarbleTexture::new(1, 2.0, 7, 5.0, low, high),
+        MarbleTexture::new(1, 2.0, 7, 5.0, low, high),
+    );
+    let worley = (
+        WorleyTexture::new(1, 2.0, low, high),
+        WorleyTexture::new(1, 2.0, low, high),
+    );
+    for p in sample_points().iter() {
+        let value = marble.0.value(0.0, 0.0, p);
+        assert_eq!(components(value), components(marble.1.value(0.0, 0.0, p)));
+        assert_eq!(
+            components(worley.0.value(0.0, 0.0, p)),
+            components(worley.1.value(0.0, 0.0, p))
+        );
+        // colors stay between the two ends
+        assert!(low.x - 1e-12 <= value.x && value.x <= high.x + 1e-12);
+    }
+}
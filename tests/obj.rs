@@ -0,0 +1,128 @@
+use std::path::PathBuf;
+
+use ray_trace::{
+    hit::Hittable,
+    interval::Interval,
+    material::materials::Lambertian,
+    model::{
+        mesh::TriangleMesh,
+        obj::{load_obj, ObjError},
+    },
+    ray::Ray,
+    vec3::Vec3,
+};
+
+// unit square in the z = 0 plane, facing +z, with corners in counterclockwise order
+const VERTICES: &str = "\
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vn 0 0 1
+";
+
+fn load(name: &str, source: &str) -> Result<TriangleMesh, ObjError> {
+    let path: PathBuf =
+        std::env::temp_dir().join(format!("ray_trace_{}_{}.obj", name, std::process::id()));
+    std::fs::write(&path, source).expect("write the OBJ file");
+    let mesh = load_obj(&path, Lambertian::new(Vec3::new(0.5, 0.5, 0.5)));
+    std::fs::remove_file(&path).ok();
+    mesh
+}
+
+// position, texture coordinate and normal indices of a face
+type Face = ([usize; 3], Option<[usize; 3]>, Option<[usize; 3]>);
+
+fn faces(mesh: &TriangleMesh) -> Vec<Face> {
+    mesh.data
+        .faces
+        .iter()
+        .map(|face| (face.positions, face.uvs, face.normals))
+        .collect()
+}
+
+#[test]
+fn full_corners_are_fan_triangulated() {
+    let mesh = load("full", &format!("{}f 1/1/1 2/2/1 3/3/1 4/4/1\n", VERTICES)).unwrap();
+    assert_eq!(
+        faces(&mesh),
+        vec![
+            ([0, 1, 2], Some([0, 1, 2]), Some([0, 0, 0])),
+            ([0, 2, 3], Some([0, 2, 3]), Some([0, 0, 0])),
+        ]
+    );
+}
+
+#[test]
+fn negative_indices_count_back_from_the_last_vertex() {
+    let absolute = load(
+        "absolute",
+        &format!("{}f 1/1/1 2/2/1 3/3/1 4/4/1\n", VERTICES),
+    )
+    .unwrap();
+    let relative = load(
+        "relative",
+        &format!("{}f -4/-4/-1 -3/-3/-1 -2/-2/-1 -1/-1/-1\n", VERTICES),
+    )
+    .unwrap();
+    assert_eq!(faces(&relative), faces(&absolute));
+
+    // relative to the vertices read so far, not to the whole file
+    let source = "v 0 0 0\nv 1 0 0\nv 1 1 0\nf -3 -2 -1\nv 5 5 5\n";
+    let mesh = load("interleaved", source).unwrap();
+    assert_eq!(faces(&mesh), vec![([0, 1, 2], None, None)]);
+}
+
+#[test]
+fn partial_corners() {
+    let mesh = load(
+        "partial",
+        &format!("{}f 1//1 2//1 3//1\nf 1/1 3/3 4/4\n", VERTICES),
+    )
+    .unwrap();
+    assert_eq!(
+        faces(&mesh),
+        vec![
+            ([0, 1, 2], None, Some([0, 0, 0])),
+            ([0, 2, 3], Some([0, 2, 3]), None),
+        ]
+    );
+}
+
+#[test]
+fn corners_interpolate_uvs_and_normals() {
+    let mesh = load("hit", &format!("{}f 1/1/1 2/2/1 3/3/1 4/4/1\n", VERTICES)).unwrap();
+    let ray = Ray::new(Vec3::new(0.25, 0.75, 1.0), Vec3::new(0.0, 0.0, -1.0));
+    let hit = mesh
+        .hit(&ray, &Interval::new(0.001, f64::INFINITY))
+        .expect("hits the square");
+    assert!((hit.t - 1.0).abs() < 1e-9);
+    assert!((hit.normal - Vec3::new(0.0, 0.0, 1.0)).length() < 1e-9);
+    assert!((hit.u - 0.25).abs() < 1e-9 && (hit.v - 0.75).abs() < 1e-9);
+}
+
+#[test]
+fn bad_indices_report_their_line() {
+    let error = load("zero", &format!("{}f 0 1 2\n", VERTICES))
+        .err()
+        .unwrap();
+    assert_eq!(error.line, Some(10));
+    let error = load("past_end", &format!("{}f 1 2 3\nf 1 2 -5\n", VERTICES))
+        .err()
+        .unwrap();
+    assert_eq!(error.line, Some(11));
+    assert!(error.message.contains("-5"), "{}", error.message);
+    let error = load("no_uvs", "v 0 0 0\nv 1 0 0\nv 1 1 0\nf 1/1 2/1 3/1\n")
+        .err()
+        .unwrap();
+    assert_eq!(error.line, Some(4));
+    assert!(
+        error.message.contains("texture coordinate"),
+        "{}",
+        error.message
+    );
+}
@@ -0,0 +1,122 @@
+use ray_trace::{camera::Camera, projection::Projection, render::get_ray, vec3::Vec3};
+
+fn assert_close(a: Vec3, b: Vec3) {
+    assert!((a - b).length() < 1e-9, "{} != {}", a, b);
+}
+
+fn direction(projection: Projection, s: f64, t: f64, aspect_ratio: f64) -> Vec3 {
+    projection
+        .direction(s, t, aspect_ratio)
+        .expect("the position is covered")
+}
+
+#[test]
+fn equirectangular_directions() {
+    let projection = Projection::Equirectangular;
+    assert_close(
+        direction(projection, 0.5, 0.5, 2.0),
+        Vec3::new(0.0, 0.0, -1.0),
+    );
+    assert_close(
+        direction(projection, 0.75, 0.5, 2.0),
+        Vec3::new(1.0, 0.0, 0.0),
+    );
+    assert_close(
+        direction(projection, 0.25, 0.5, 2.0),
+        Vec3::new(-1.0, 0.0, 0.0),
+    );
+    assert_close(
+        direction(projection, 0.0, 0.5, 2.0),
+        Vec3::new(0.0, 0.0, 1.0),
+    );
+    assert_close(
+        direction(projection, 0.3, 0.0, 2.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+    assert_close(
+        direction(projection, 0.3, 1.0, 2.0),
+        Vec3::new(0.0, -1.0, 0.0),
+    );
+}
+
+#[test]
+fn fisheye_directions() {
+    let projection = Projection::Fisheye { fov: 180.0 };
+    assert_close(
+        direction(projection, 0.5, 0.5, 1.0),
+        Vec3::new(0.0, 0.0, -1.0),
+    );
+    // the image circle touches the sides at 90 degrees off the view
+    assert_close(
+        direction(projection, 1.0, 0.5, 1.0),
+        Vec3::new(1.0, 0.0, 0.0),
+    );
+    assert_close(
+        direction(projection, 0.5, 0.0, 1.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+    // halfway out is halfway round
+    let half = direction(projection, 0.75, 0.5, 1.0);
+    assert_close(half, Vec3::new(1.0, 0.0, -1.0).unit());
+    // corners lie outside the circle
+    assert!(projection.direction(0.0, 0.0, 1.0).is_none());
+    // on a wide image the circle fits the height
+    assert_close(
+        direction(projection, 0.75, 0.5, 2.0),
+        Vec3::new(1.0, 0.0, 0.0),
+    );
+}
+
+#[test]
+fn cube_map_face_centers() {
+    let faces = [
+        (1.0 / 6.0, 0.25, Vec3::new(1.0, 0.0, 0.0)),
+        (0.5, 0.25, Vec3::new(-1.0, 0.0, 0.0)),
+        (5.0 / 6.0, 0.25, Vec3::new(0.0, 1.0, 0.0)),
+        (1.0 / 6.0, 0.75, Vec3::new(0.0, -1.0, 0.0)),
+        (0.5, 0.75, Vec3::new(0.0, 0.0, 1.0)),
+        (5.0 / 6.0, 0.75, Vec3::new(0.0, 0.0, -1.0)),
+    ];
+    for (s, t, expected) in faces {
+        assert_close(direction(Projection::CubeMap, s, t, 1.5), expected);
+    }
+    // neighbouring faces meet at 45 degrees
+    let edge = direction(Projection::CubeMap, 1.0 / 3.0 - 1e-12, 0.25, 1.5);
+    assert!((edge.dot(&Vec3::new(1.0, 0.0, 0.0)) - 0.5f64.sqrt()).abs() < 1e-6);
+}
+
+#[test]
+fn orthographic_rays_are_parallel() {
+    let mut camera = Camera::new(
+        (16, 9),
+        "unused.png",
+        10,
+        1,
+        90.0,
+        (
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(1.0, 2.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        ),
+        (0.0, 3.0),
+    )
+    .expect("valid camera");
+    camera
+        .set_projection(Projection::Orthographic { height: 4.5 })
+        .expect("valid projection");
+    let (mut lowest, mut highest) = (f64::INFINITY, f64::NEG_INFINITY);
+    for (col, row) in [(0, 0), (15, 0), (0, 8), (15, 8), (7, 4)] {
+        let ray = get_ray(col, row, &camera.config).expect("orthographic covers the image");
+        assert_close(ray.direction.unit(), Vec3::new(0.0, 0.0, -1.0));
+        // rays start in the plane of the camera
+        assert!((ray.origin.z - 3.0).abs() < 1e-9, "{}", ray.origin);
+        lowest = lowest.min(ray.origin.y);
+        highest = highest.max(ray.origin.y);
+    }
+    // 4.5 units high, minus the half pixel of jitter at most on either side
+    assert!(
+        highest - lowest > 4.5 * 7.0 / 9.0 && highest - lowest < 4.5,
+        "{}",
+        highest - lowest
+    );
+}
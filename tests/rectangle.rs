@@ -0,0 +1,148 @@
+use ray_trace::{
+    hit::{Hittable, HittableList},
+    interval::Interval,
+    material::materials::Lambertian,
+    model::rectangle::Rectangle,
+    ray::Ray,
+    vec3::Vec3,
+};
+
+// unit square in the y = 0 plane, facing up
+fn floor() -> Rectangle {
+    Rectangle::new(
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(1.0, 0.0, 1.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Lambertian::new(Vec3::new(0.5, 0.5, 0.5)),
+    )
+}
+
+fn forward() -> Interval {
+    Interval::new(0.001, f64::INFINITY)
+}
+
+fn assert_close(a: Vec3, b: Vec3) {
+    assert!((a - b).length() < 1e-9, "{} != {}", a, b);
+}
+
+#[test]
+fn t_is_the_ray_parameter() {
+    let rectangle = floor();
+    let origin = Vec3::new(0.5, 2.0, 0.5);
+    for scale in [0.25, 1.0, 8.0] {
+        let ray = Ray::new(origin, Vec3::new(0.0, -1.0, 0.0) * scale);
+        let hit = rectangle
+            .hit(&ray, &forward())
+            .expect("ray points at the rectangle");
+        assert!((hit.t - 2.0 / scale).abs() < 1e-12);
+        assert_close(ray.at(hit.t), hit.p);
+        assert_close(hit.p, Vec3::new(0.5, 0.0, 0.5));
+    }
+}
+
+#[test]
+fn closest_hit_with_non_unit_direction() {
+    let material = Lambertian::new(Vec3::new(0.5, 0.5, 0.5));
+    let mut world = HittableList::new();
+    world.add(Box::new(Rectangle::new(
+        Vec3::new(-1.0, 1.0, -1.0),
+        Vec3::new(1.0, 1.0, 1.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        material.clone(),
+    )));
+    world.add(Box::new(Rectangle::new(
+        Vec3::new(-1.0, 0.0, -1.0),
+        Vec3::new(1.0, 0.0, 1.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        material,
+    )));
+
+    let ray = Ray::new(Vec3::new(0.0, 3.0, 0.0), Vec3::new(0.0, -10.0, 0.0));
+    let hit = world
+        .hit(&ray, &forward())
+        .expect("ray points at both rectangles");
+    assert!((hit.p.y - 1.0).abs() < 1e-12);
+    assert!((hit.t - 0.2).abs() < 1e-12);
+}
+
+#[test]
+fn parallel_rays_miss() {
+    let rectangle = floor();
+    // above the plane
+    let ray = Ray::new(Vec3::new(-1.0, 1.0, 0.5), Vec3::new(1.0, 0.0, 0.0));
+    assert!(rectangle.hit(&ray, &forward()).is_none());
+    // inside the plane
+    let ray = Ray::new(Vec3::new(-1.0, 0.0, 0.5), Vec3::new(1.0, 0.0, 0.0));
+    assert!(rectangle.hit(&ray, &forward()).is_none());
+}
+
+#[test]
+fn hits_are_limited_by_the_interval() {
+    let rectangle = floor();
+    let ray = Ray::new(Vec3::new(0.5, 1.0, 0.5), Vec3::new(0.0, 1.0, 0.0));
+    assert!(rectangle.hit(&ray, &forward()).is_none());
+    // behind the origin, like every other primitive
+    let hit = rectangle
+        .hit(&ray, &Interval::new(f64::NEG_INFINITY, f64::INFINITY))
+        .expect("the interval reaches behind the origin");
+    assert!((hit.t + 1.0).abs() < 1e-12);
+    assert!(rectangle.hit(&ray, &Interval::new(-0.5, 0.5)).is_none());
+}
+
+#[test]
+fn grazing_rays() {
+    let rectangle = floor();
+    // one unit of drop over a million units of travel
+    let origin = Vec3::new(-1e6 + 0.5, 1.0, 0.5);
+    let ray = Ray::new(origin, Vec3::new(1e6, -1.0, 0.0));
+    let hit = rectangle
+        .hit(&ray, &forward())
+        .expect("grazing ray lands inside");
+    assert!((hit.t - 1.0).abs() < 1e-9);
+    assert!((hit.p - Vec3::new(0.5, 0.0, 0.5)).length() < 1e-6);
+    assert!(hit.front_face);
+
+    // same angle, but landing just past the far edge
+    let ray = Ray::new(origin + Vec3::new(0.6, 0.0, 0.0), Vec3::new(1e6, -1.0, 0.0));
+    assert!(rectangle.hit(&ray, &forward()).is_none());
+}
+
+#[test]
+fn edges_are_inside() {
+    let rectangle = floor();
+    for (x, z) in [(0.0, 0.5), (1.0, 0.5), (0.5, 0.0), (0.5, 1.0), (1.0, 1.0)] {
+        let ray = Ray::new(Vec3::new(x, 1.0, z), Vec3::new(0.0, -1.0, 0.0));
+        assert!(rectangle.hit(&ray, &forward()).is_some(), "({}, {})", x, z);
+    }
+}
+
+#[test]
+fn back_face_and_uv() {
+    let rectangle = floor();
+    let ray = Ray::new(Vec3::new(0.25, -1.0, 0.75), Vec3::new(0.0, 1.0, 0.0));
+    let hit = rectangle.hit(&ray, &forward()).expect("hit from below");
+    assert!(!hit.front_face);
+    assert_close(hit.normal, Vec3::new(0.0, -1.0, 0.0));
+    assert!((hit.u - 0.25).abs() < 1e-12);
+    assert!((hit.v - 0.25).abs() < 1e-12);
+}
+
+#[test]
+fn bounding_box_contains_tilted_rectangle() {
+    // corners share x, the tilted plane makes x vary over the surface
+    let rectangle = Rectangle::new(
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 2.0, 2.0),
+        Vec3::new(1.0, 0.0, 1.0),
+        Lambertian::new(Vec3::new(0.5, 0.5, 0.5)),
+    );
+    let bbox = rectangle.bounding_box();
+    assert!(bbox.x.min.is_finite() && bbox.x.max.is_finite());
+    for (y, z) in [(0.0, 0.0), (2.0, 0.0), (0.0, 2.0), (1.0, 1.5)] {
+        let ray = Ray::new(Vec3::new(10.0, y, z), Vec3::new(-1.0, 0.0, 0.0));
+        let hit = rectangle
+            .hit(&ray, &forward())
+            .expect("ray crosses the plane inside");
+        assert!(bbox.x.contains(hit.p.x) && bbox.y.contains(hit.p.y) && bbox.z.contains(hit.p.z));
+    }
+}
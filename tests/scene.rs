@@ -0,0 +1,176 @@
+use ray_trace::{
+    hit::Hittable,
+    interval::Interval,
+    ray::Ray,
+    scene::{parse_scene, SceneError},
+    vec3::Vec3,
+};
+
+const CAMERA: &str = r#"
+[camera]
+width = 16
+height = 9
+look_from = [0.0, 0.0, 0.0]
+look_at = [0.0, 0.0, -1.0]
+
+[materials.gray]
+type = "lambertian"
+albedo = [0.5, 0.5, 0.5]
+"#;
+
+fn parse_error(source: &str) -> SceneError {
+    parse_scene(source).err().expect("the scene should not load")
+}
+
+#[test]
+fn unknown_material_reports_its_position() {
+    let source = format!(
+        "{}\n[[objects]]\ntype = \"sphere\"\ncenter = [0.0, 0.0, -1.0]\nradius = 0.5\nmaterial = \"grey\"\n",
+        CAMERA
+    );
+    let error = parse_error(&source);
+
+    let line = source
+        .lines()
+        .position(|line| line.starts_with("material = \"grey\""))
+        .unwrap()
+        + 1;
+    assert_eq!(error.line, Some(line));
+    assert_eq!(error.column, Some("material = ".len() + 1));
+    assert!(error.message.contains("objects[0].material"), "{}", error.message);
+    assert!(error.message.contains("`grey`"), "{}", error.message);
+    assert!(error.to_string().starts_with(&format!("line {}, column 12: ", line)));
+}
+
+#[test]
+fn loaded_objects_are_hit() {
+    let source = format!(
+        "{}\n[[objects]]\ntype = \"sphere\"\ncenter = [0.0, 0.0, -2.0]\nradius = 0.5\nmaterial = \"gray\"\n\
+         \n[[objects]]\ntype = \"sphere\"\ncenter = [3.0, 0.0, -2.0]\nradius = 0.5\nmaterial = \"gray\"\n",
+        CAMERA
+    );
+    let scene = parse_scene(&source).expect("the scene should load");
+    let forward = Interval::new(0.001, f64::INFINITY);
+
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+    let hit = scene.world.hit(&ray, &forward).expect("hits the first sphere");
+    assert!((hit.t - 1.5).abs() < 1e-9);
+    let ray = Ray::new(Vec3::new(3.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+    let hit = scene.world.hit(&ray, &forward).expect("hits the second sphere");
+    assert!((hit.p - Vec3::new(3.0, 0.0, -1.5)).length() < 1e-9);
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+    assert!(scene.world.hit(&ray, &forward).is_none());
+}
+
+#[test]
+fn environment_colors_missed_rays() {
+    let up = Ray::new(Vec3::new_zero(), Vec3::new(0.0, 1.0, 0.0));
+    let scene = parse_scene(CAMERA).expect("the scene should load");
+    assert!(scene.environment.is_none());
+
+    let source = format!(
+        "{}\n[environment]\ntype = \"solid\"\ncolor = [0.0, 0.0, 0.0]\n",
+        CAMERA
+    );
+    let scene = parse_scene(&source).expect("the scene should load");
+    let environment = scene.environment.expect("a solid environment");
+    assert!(environment.color(&up).near_zero());
+
+    let source = format!(
+        "{}\n[environment]\ntype = \"gradient\"\nzenith = [0.0, 0.0, 2.0]\n",
+        CAMERA
+    );
+    let scene = parse_scene(&source).expect("the scene should load");
+    let environment = scene.environment.expect("a gradient environment");
+    assert!((environment.color(&up) - Vec3::new(0.0, 0.0, 2.0)).length() < 1e-9);
+    let down = Ray::new(Vec3::new_zero(), Vec3::new(0.0, -1.0, 0.0));
+    assert!((environment.color(&down) - Vec3::new(1.0, 1.0, 1.0)).length() < 1e-9);
+}
+
+#[test]
+fn bad_environments_report_their_position() {
+    let source = format!("{}\n[environment]\ntype = \"starry\"\n", CAMERA);
+    let error = parse_error(&source);
+    let line = source.lines().count();
+    assert_eq!(error.line, Some(line));
+    assert!(error.message.contains("`starry`"), "{}", error.message);
+
+    let source = format!(
+        "{}\n[environment]\ntype = \"image\"\npath = \"missing.hdr\"\n",
+        CAMERA
+    );
+    let error = parse_error(&source);
+    assert_eq!(error.line, Some(source.lines().count()));
+    assert!(error.message.contains("environment.path"), "{}", error.message);
+}
+
+#[test]
+fn octaves_are_only_taken_by_turbulent_textures() {
+    let texture = |kind: &str, octaves: u32| {
+        format!(
+            "{}\n[textures.grain]\ntype = \"{}\"\noctaves = {}\n",
+            CAMERA, kind, octaves
+        )
+    };
+    for kind in ["turbulence", "marble", "wood"] {
+        parse_scene(&texture(kind, 3)).expect("the scene should load");
+    }
+
+    let source = texture("worley", 3);
+    let error = parse_error(&source);
+    assert_eq!(error.line, Some(source.lines().count()));
+    assert!(
+        error.message.contains("textures.grain.octaves"),
+        "{}",
+        error.message
+    );
+    let source = texture("marble", 0);
+    let error = parse_error(&source);
+    assert_eq!(error.line, Some(source.lines().count()));
+}
+
+#[test]
+fn non_finite_camera_values_report_their_position() {
+    // the camera table ends at the blank line before the materials
+    let camera = |key: &str| CAMERA.replacen("\n\n", &format!("\n{}\n\n", key), 1);
+    for key in [
+        "shutter_open = nan",
+        "shutter_close = inf",
+        "aperture = \"polygon\"\nblade_rotation = nan",
+    ] {
+        let source = camera(key);
+        let error = parse_error(&source);
+        let line = source
+            .lines()
+            .position(|line| line == key.lines().last().unwrap())
+            .unwrap()
+            + 1;
+        assert_eq!(error.line, Some(line), "{}", key);
+    }
+}
+
+#[test]
+fn media_need_a_positive_density() {
+    for density in ["0.0", "-1.0", "nan"] {
+        let source = format!(
+            "{}\n[[objects]]\ntype = \"sphere\"\ncenter = [0.0, 0.0, -2.0]\nradius = 0.5\nmaterial = \"gray\"\ndensity = {}\n",
+            CAMERA, density
+        );
+        let error = parse_error(&source);
+        assert_eq!(error.line, Some(source.lines().count()), "{}", density);
+        assert!(error.message.contains("objects[0].density"), "{}", error.message);
+    }
+}
+
+#[test]
+fn scale_factors_must_be_invertible() {
+    for scale in ["[1.0, 0.0, 1.0]", "[1e-320, 1.0, 1.0]"] {
+        let source = format!(
+            "{}\n[[objects]]\ntype = \"sphere\"\ncenter = [0.0, 0.0, -2.0]\nradius = 0.5\nmaterial = \"gray\"\nscale = {}\n",
+            CAMERA, scale
+        );
+        let error = parse_error(&source);
+        assert_eq!(error.line, Some(source.lines().count()), "{}", scale);
+        assert!(error.message.contains("objects[0].scale"), "{}", error.message);
+    }
+}
@@ -0,0 +1,171 @@
+use ray_trace::{
+    camera::{Camera, CameraBuilder},
+    hit::HittableList,
+    material::materials::Lambertian,
+    model::sphere::Sphere,
+    projection::Projection,
+    ray::Ray,
+    render::get_ray,
+    rng,
+    stereo::{Stereo, StereoLayout},
+    vec3::Vec3,
+};
+
+// 2x 8x8 eyes looking down -z, focused 2 units away
+fn stereo_camera(stereo: Stereo) -> Camera {
+    let mut camera = Camera::new(
+        (16, 8),
+        "unused.png",
+        10,
+        1,
+        60.0,
+        (
+            Vec3::new_zero(),
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        ),
+        (0.0, 2.0),
+    )
+    .expect("valid camera");
+    camera.set_stereo(stereo).expect("valid stereo");
+    camera
+}
+
+// left and right eye rays through the same pixel of each view, same jitter
+fn eye_rays(camera: &Camera, col: u32, row: u32) -> (Ray, Ray) {
+    rng::seed(22);
+    let left = get_ray(col, row, &camera.config).expect("perspective covers the image");
+    rng::seed(22);
+    let right = get_ray(col + 8, row, &camera.config).expect("perspective covers the image");
+    (left, right)
+}
+
+#[test]
+fn eyes_sit_half_the_ipd_apart() {
+    let camera = stereo_camera(
+        Stereo::new(0.064, Some(f64::INFINITY), StereoLayout::SideBySide).expect("valid stereo"),
+    );
+    for (col, row) in [(0, 0), (3, 5), (7, 7)] {
+        let (left, right) = eye_rays(&camera, col, row);
+        assert!((left.origin - Vec3::new(-0.032, 0.0, 0.0)).length() < 1e-12);
+        assert!((right.origin - Vec3::new(0.032, 0.0, 0.0)).length() < 1e-12);
+        // parallel views
+        assert!((left.direction.unit() - right.direction.unit()).length() < 1e-12);
+    }
+}
+
+#[test]
+fn views_meet_at_the_convergence_distance() {
+    let camera = stereo_camera(
+        Stereo::new(0.064, Some(2.0), StereoLayout::SideBySide).expect("valid stereo"),
+    );
+    for (col, row) in [(0, 0), (3, 5), (7, 7)] {
+        let (left, right) = eye_rays(&camera, col, row);
+        // both rays reach the same point on the focus plane
+        let (left, right) = (left.at(1.0), right.at(1.0));
+        assert!((left - right).length() < 1e-12, "{} {}", left, right);
+        assert!((left.z + 2.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn top_bottom_puts_the_left_eye_on_top() {
+    let stereo =
+        Stereo::new(0.064, Some(f64::INFINITY), StereoLayout::TopBottom).expect("valid stereo");
+    assert_eq!(stereo.eye_size((16, 8)), (16, 4));
+    assert_eq!(stereo.eye(5, 3, (16, 8)), (-1.0, 5, 3));
+    assert_eq!(stereo.eye(5, 4, (16, 8)), (1.0, 5, 0));
+    let side_by_side =
+        Stereo::new(0.064, Some(f64::INFINITY), StereoLayout::SideBySide).expect("valid stereo");
+    assert_eq!(side_by_side.eye(8, 3, (16, 8)), (1.0, 0, 3));
+}
+
+// 360x90 per eye, so one degree of longitude per column, looking down -z
+fn ods_camera() -> Camera {
+    CameraBuilder::new()
+        .image_size(720, 90)
+        .projection(Projection::Equirectangular)
+        .stereo(
+            Stereo::new(2.0, Some(f64::INFINITY), StereoLayout::SideBySide).expect("valid stereo"),
+        )
+        .build()
+        .expect("valid camera")
+}
+
+// origins of the left and right eye rays through the view column `col`
+fn eye_origins(camera: &Camera, col: u32) -> [(Vec3, Vec3); 2] {
+    let left = get_ray(col, 45, &camera.config).expect("panoramas cover the image");
+    let right = get_ray(360 + col, 45, &camera.config).expect("panoramas cover the image");
+    [
+        (left.origin, left.direction.unit()),
+        (right.origin, right.direction.unit()),
+    ]
+}
+
+#[test]
+fn ods_eyes_are_perpendicular_to_the_view() {
+    let camera = ods_camera();
+    // longitude 0 and 90 degrees
+    for col in [180, 270] {
+        for (origin, direction) in eye_origins(&camera, col) {
+            assert!((origin.length() - 1.0).abs() < 1e-9, "{}", origin);
+            assert!(
+                origin.dot(&direction).abs() < 1e-9,
+                "{} . {}",
+                origin,
+                direction
+            );
+        }
+    }
+}
+
+#[test]
+fn ods_eyes_turn_with_the_longitude() {
+    let camera = ods_camera();
+
+    // looking forward the eyes sit left and right along x
+    let [(left, _), (right, _)] = eye_origins(&camera, 180);
+    assert!(left.x < -0.99 && right.x > 0.99, "{} {}", left, right);
+
+    // looking along +x the left eye is in front (-z), the right one behind
+    let [(left, direction), (right, _)] = eye_origins(&camera, 270);
+    assert!(direction.x > 0.99, "{}", direction);
+    assert!(left.z < -0.99 && right.z > 0.99, "{} {}", left, right);
+    // the two rays jitter within their pixels independently
+    assert!((left + right).length() < 0.05);
+}
+
+#[test]
+fn convergence_follows_autofocus() {
+    // the sphere's front is 4 units ahead, the camera starts focused at 1
+    let mut world = HittableList::new();
+    world.add(Box::new(Sphere::new(
+        Vec3::new(0.0, 0.0, -5.0),
+        1.0,
+        Lambertian::new(Vec3::new(0.5, 0.5, 0.5)),
+    )));
+    let camera = CameraBuilder::new()
+        .image_size(202, 101)
+        .focus_distance(1.0)
+        .stereo(Stereo::new(2.0, None, StereoLayout::SideBySide).expect("valid stereo"))
+        .autofocus((0.5, 0.5))
+        .build()
+        .expect("valid camera");
+    let config = camera.focused_config(&world);
+    assert!((config.focus_dist - 4.0).abs() < 1e-9);
+
+    // the center rays of both eyes meet on the focus plane, within the
+    // jitter of a pixel there
+    let left = get_ray(50, 50, &config).expect("perspective covers the image");
+    let right = get_ray(151, 50, &config).expect("perspective covers the image");
+    let (left, right) = (left.at(1.0), right.at(1.0));
+    assert!((left.z + 4.0).abs() < 1e-9 && (right.z + 4.0).abs() < 1e-9);
+    let pixel = 8.0 / 101.0;
+    assert!((left - right).length() < 2.0 * pixel, "{} {}", left, right);
+    assert!(
+        left.x.abs() < pixel && right.x.abs() < pixel,
+        "{} {}",
+        left,
+        right
+    );
+}
@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+
+use image::{Rgb, RgbImage};
+use ray_trace::{
+    model::sphere::Sphere,
+    texture::textures::{CheckerTexture, ImageTexture, SolidColor, Texture},
+    vec3::Vec3,
+};
+
+fn assert_close(a: Vec3, b: Vec3) {
+    assert!((a - b).length() < 1e-9, "{} != {}", a, b);
+}
+
+fn srgb_to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+// 2x2 image: red, green on top, blue, mid gray below
+fn load_image_texture() -> std::sync::Arc<ImageTexture> {
+    let path: PathBuf =
+        std::env::temp_dir().join(format!("ray_trace_{}_texture.png", std::process::id()));
+    let mut image = RgbImage::new(2, 2);
+    image.put_pixel(0, 0, Rgb([255, 0, 0]));
+    image.put_pixel(1, 0, Rgb([0, 255, 0]));
+    image.put_pixel(0, 1, Rgb([0, 0, 255]));
+    image.put_pixel(1, 1, Rgb([128, 128, 128]));
+    image.save(&path).expect("write the texture");
+    let texture = ImageTexture::load(&path).expect("load the texture");
+    std::fs::remove_file(&path).ok();
+    texture
+}
+
+#[test]
+fn solid_color_ignores_the_coordinates() {
+    let color = Vec3::new(0.1, 0.2, 0.3);
+    let texture = SolidColor::new(color);
+    assert_close(texture.value(0.3, 0.7, &Vec3::new(5.0, -1.0, 2.0)), color);
+}
+
+#[test]
+fn checker_alternates_squares() {
+    let (even, odd) = (Vec3::new(1.0, 1.0, 1.0), Vec3::new(0.0, 0.0, 0.0));
+    // 4 squares per unit, each 0.25 wide
+    let checker = CheckerTexture::new_colors(4.0, even, odd);
+    let p = Vec3::new_zero();
+    assert_close(checker.value(0.1, 0.1, &p), even);
+    assert_close(checker.value(0.3, 0.1, &p), odd);
+    assert_close(checker.value(0.1, 0.3, &p), odd);
+    assert_close(checker.value(0.3, 0.3, &p), even);
+    // the pattern continues past [0, 1], also below 0
+    assert_close(checker.value(-0.1, 0.1, &p), odd);
+    assert_close(checker.value(-0.1, -0.1, &p), even);
+}
+
+#[test]
+fn image_texels_are_linear_and_v_points_up() {
+    let texture = load_image_texture();
+    let p = Vec3::new_zero();
+    // texel centers, the top row of the image at v = 0.75
+    assert_close(texture.value(0.25, 0.75, &p), Vec3::new(1.0, 0.0, 0.0));
+    assert_close(texture.value(0.75, 0.75, &p), Vec3::new(0.0, 1.0, 0.0));
+    assert_close(texture.value(0.25, 0.25, &p), Vec3::new(0.0, 0.0, 1.0));
+    let gray = srgb_to_linear(128);
+    assert_close(texture.value(0.75, 0.25, &p), Vec3::new(gray, gray, gray));
+}
+
+#[test]
+fn image_lookups_filter_and_repeat() {
+    let texture = load_image_texture();
+    let p = Vec3::new_zero();
+    // halfway between the red and green texel centers
+    assert_close(texture.value(0.5, 0.75, &p), Vec3::new(0.5, 0.5, 0.0));
+    // coordinates outside [0, 1] wrap around
+    assert_close(texture.value(1.25, 0.75, &p), texture.value(0.25, 0.75, &p));
+    assert_close(
+        texture.value(0.75, -0.75, &p),
+        texture.value(0.75, 0.25, &p),
+    );
+}
+
+#[test]
+fn sphere_uv_wraps_around_y() {
+    let uv = |x: f64, y: f64, z: f64| Sphere::uv(&Vec3::new(x, y, z));
+    let close = |(u, v): (f64, f64), (eu, ev): (f64, f64)| {
+        assert!(
+            (u - eu).abs() < 1e-9 && (v - ev).abs() < 1e-9,
+            "({}, {})",
+            u,
+            v
+        );
+    };
+    close(uv(1.0, 0.0, 0.0), (0.5, 0.5));
+    close(uv(0.0, 0.0, 1.0), (0.25, 0.5));
+    close(uv(0.0, 0.0, -1.0), (0.75, 0.5));
+    close(uv(0.0, 1.0, 0.0), (uv(0.0, 1.0, 0.0).0, 1.0));
+    close(uv(0.0, -1.0, 0.0), (uv(0.0, -1.0, 0.0).0, 0.0));
+}
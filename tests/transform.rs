@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use ray_trace::{
+    hit::{HitRecord, Hittable},
+    interval::Interval,
+    material::materials::Lambertian,
+    model::{quad::Quad, sphere::Sphere, transformed::Transformed, triangle::Triangle},
+    ray::Ray,
+    rng::{self, random},
+    transform::{Matrix4, Transform},
+    vec3::Vec3,
+};
+
+fn assert_matrix_close(a: &Matrix4, b: &Matrix4) {
+    for i in 0..4 {
+        for j in 0..4 {
+            assert!((a.m[i][j] - b.m[i][j]).abs() < 1e-9, "{:?} != {:?}", a, b);
+        }
+    }
+}
+
+fn placement() -> Transform {
+    Transform::translate(Vec3::new(1.0, -2.0, 3.0))
+        * Transform::rotate(Vec3::new(1.0, 1.0, 0.0), 35.0)
+        * Transform::scale(Vec3::new(2.0, 0.5, 1.5))
+}
+
+#[test]
+fn inverse_round_trips() {
+    let general = Matrix4::new([
+        [2.0, 1.0, 0.0, 3.0],
+        [0.5, -1.0, 4.0, 0.0],
+        [1.0, 0.0, 1.0, -2.0],
+        [0.0, 2.0, 0.5, 1.0],
+    ]);
+    for matrix in [general, placement().matrix] {
+        let inverse = matrix.inverse().expect("invertible");
+        assert_matrix_close(&(matrix * inverse), &Matrix4::identity());
+        assert_matrix_close(&(inverse * matrix), &Matrix4::identity());
+        assert_matrix_close(&inverse.inverse().unwrap(), &matrix);
+    }
+
+    // the last row repeats the first
+    let singular = Matrix4::new([
+        [1.0, 2.0, 3.0, 4.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [1.0, 2.0, 3.0, 4.0],
+    ]);
+    assert!(singular.inverse().is_none());
+    assert!(Transform::new(singular).is_none());
+}
+
+#[test]
+fn transform_inverse_matches_the_matrix() {
+    let transform = placement();
+    assert_matrix_close(&transform.inverted().matrix, &transform.inverse);
+    let p = Vec3::new(0.3, -1.2, 2.5);
+    assert!((transform.inverted().point(&transform.point(&p)) - p).length() < 1e-9);
+}
+
+// rays from around the object towards points near it, some of them miss
+fn random_rays(center: Vec3, count: usize) -> Vec<Ray> {
+    rng::seed(11);
+    let spread = |scale: f64| {
+        Vec3::new(
+            random::<f64>() - 0.5,
+            random::<f64>() - 0.5,
+            random::<f64>() - 0.5,
+        ) * scale
+    };
+    (0..count)
+        .map(|_| {
+            let origin = center + spread(20.0);
+            let target = center + spread(6.0);
+            Ray::new(origin, target - origin)
+        })
+        .collect()
+}
+
+fn assert_same_hit(a: Option<HitRecord>, b: Option<HitRecord>) {
+    match (a, b) {
+        (None, None) => {}
+        (Some(a), Some(b)) => {
+            assert!((a.t - b.t).abs() < 1e-9, "t {} != {}", a.t, b.t);
+            assert!((a.p - b.p).length() < 1e-9, "p {} != {}", a.p, b.p);
+            assert!(
+                (a.normal - b.normal).length() < 1e-9,
+                "normal {} != {}",
+                a.normal,
+                b.normal
+            );
+            assert_eq!(a.front_face, b.front_face);
+        }
+        (a, b) => panic!("hit {} != {}", a.is_some(), b.is_some()),
+    }
+}
+
+#[test]
+fn transformed_sphere_matches_the_placed_sphere() {
+    let material = Lambertian::new(Vec3::new(0.5, 0.5, 0.5));
+    let unit = Arc::new(Sphere::new(Vec3::new_zero(), 1.0, material.clone()));
+    let transform = Transform::translate(Vec3::new(1.0, -2.0, 3.0))
+        * Transform::rotate_y(70.0)
+        * Transform::uniform_scale(2.0);
+    let instance = Transformed::new(unit, transform);
+    let placed = Sphere::new(Vec3::new(1.0, -2.0, 3.0), 2.0, material);
+
+    let forward = Interval::new(0.001, f64::INFINITY);
+    let rays = random_rays(Vec3::new(1.0, -2.0, 3.0), 500);
+    assert!(rays.iter().any(|ray| placed.hit(ray, &forward).is_some()));
+    for ray in rays.iter() {
+        assert_same_hit(instance.hit(ray, &forward), placed.hit(ray, &forward));
+    }
+}
+
+#[test]
+fn transformed_triangle_matches_the_transformed_corners() {
+    // non-uniform scale, normals need the inverse transpose
+    let material = Lambertian::new(Vec3::new(0.5, 0.5, 0.5));
+    let corners = [
+        Vec3::new(-1.0, 0.0, -1.0),
+        Vec3::new(1.0, 0.0, -0.5),
+        Vec3::new(-1.0, 0.3, 1.0),
+    ];
+    let transform = placement();
+    let instance = Transformed::new(
+        Arc::new(Triangle::new(
+            corners[0],
+            corners[1],
+            corners[2],
+            material.clone(),
+        )),
+        transform,
+    );
+    let [a, b, c] = corners.map(|corner| transform.point(&corner));
+    let placed = Triangle::new(a, b, c, material);
+
+    let forward = Interval::new(0.001, f64::INFINITY);
+    let rays = random_rays(transform.point(&Vec3::new_zero()), 500);
+    assert!(rays.iter().any(|ray| placed.hit(ray, &forward).is_some()));
+    for ray in rays.iter() {
+        let (found, expected) = (instance.hit(ray, &forward), placed.hit(ray, &forward));
+        assert_eq!(found.is_some(), expected.is_some());
+        let (Some(found), Some(expected)) = (found, expected) else {
+            continue;
+        };
+        assert!(
+            (found.t - expected.t).abs() < 1e-9,
+            "t {} != {}",
+            found.t,
+            expected.t
+        );
+        assert!(
+            (found.p - expected.p).length() < 1e-9,
+            "p {} != {}",
+            found.p,
+            expected.p
+        );
+        // a unit normal perpendicular to the placed triangle
+        assert!((found.normal.length() - 1.0).abs() < 1e-9);
+        assert!(found.normal.dot(&(b - a)).abs() < 1e-9);
+        assert!(found.normal.dot(&(c - a)).abs() < 1e-9);
+        assert!(instance
+            .bounding_box()
+            .hit(ray, &Interval::new(0.001, found.t + 1e-6)));
+    }
+}
+
+#[test]
+fn transformed_quad_matches_the_transformed_corners() {
+    // non-uniform scale, normals need the inverse transpose
+    let material = Lambertian::new(Vec3::new(0.5, 0.5, 0.5));
+    let (origin, u, v) = (
+        Vec3::new(-1.0, 0.0, -1.0),
+        Vec3::new(2.0, 0.0, 0.5),
+        Vec3::new(0.0, 0.3, 2.0),
+    );
+    let transform = placement();
+    let instance = Transformed::new(
+        Arc::new(Quad::new(origin, u, v, material.clone())),
+        transform,
+    );
+    let placed = Quad::new(
+        transform.point(&origin),
+        transform.vector(&u),
+        transform.vector(&v),
+        material,
+    );
+
+    let forward = Interval::new(0.001, f64::INFINITY);
+    let rays = random_rays(transform.point(&Vec3::new_zero()), 500);
+    assert!(rays.iter().any(|ray| placed.hit(ray, &forward).is_some()));
+    for ray in rays.iter() {
+        assert_same_hit(instance.hit(ray, &forward), placed.hit(ray, &forward));
+        if let Some(hit) = instance.hit(ray, &forward) {
+            assert!(instance
+                .bounding_box()
+                .hit(ray, &Interval::new(0.001, hit.t + 1e-6)));
+        }
+    }
+}
+
+#[test]
+fn quad_hits_do_not_depend_on_the_direction_length() {
+    // shrinking transforms leave rays with short directions
+    let material = Lambertian::new(Vec3::new(0.5, 0.5, 0.5));
+    let quad = Quad::new(
+        Vec3::new(-1.0, -1.0, -1.0),
+        Vec3::new(2.0, 0.0, 0.0),
+        Vec3::new(0.0, 2.0, 0.0),
+        material,
+    );
+    let forward = Interval::new(0.001, f64::INFINITY);
+    let direction = Vec3::new(0.2, 0.1, -1.0);
+    let expected = quad
+        .hit(&Ray::new(Vec3::new_zero(), direction), &forward)
+        .expect("hits the quad");
+    let short = quad
+        .hit(&Ray::new(Vec3::new_zero(), direction * 1e-9), &forward)
+        .expect("a short direction hits the quad too");
+    assert!((short.p - expected.p).length() < 1e-9);
+    assert!((short.t * 1e-9 - expected.t).abs() < 1e-9);
+}
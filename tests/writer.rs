@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use ray_trace::{
+    vec3::Vec3,
+    writer::{write_exr, write_pfm, OutputFormat},
+};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ray_trace_{}_{}", std::process::id(), name))
+}
+
+// 3x2 film, every channel different and outside [0, 1] in places
+fn film() -> Vec<Vec<Vec3>> {
+    vec![
+        vec![
+            Vec3::new(0.0, 0.5, 1.0),
+            Vec3::new(2.0, 3.5, 0.25),
+            Vec3::new(10.0, 0.0, 0.125),
+        ],
+        vec![
+            Vec3::new(-1.0, 0.75, 4.0),
+            Vec3::new(0.0625, 100.0, 0.0),
+            Vec3::new(1.5, 1.5, 1.5),
+        ],
+    ]
+}
+
+#[test]
+fn output_format_follows_the_extension() {
+    assert_eq!(OutputFormat::from_path("a/b.exr"), OutputFormat::Exr);
+    assert_eq!(OutputFormat::from_path("image.PFM"), OutputFormat::Pfm);
+    assert_eq!(OutputFormat::from_path("image.png"), OutputFormat::Ldr);
+    assert_eq!(OutputFormat::from_path("image"), OutputFormat::Ldr);
+}
+
+#[test]
+fn pfm_is_little_endian_and_bottom_up() {
+    let path = temp_path("film.pfm");
+    write_pfm(&path, &film()).expect("write the PFM file");
+    let bytes = std::fs::read(&path).expect("read the PFM file back");
+    std::fs::remove_file(&path).ok();
+
+    // a negative scale marks little endian data
+    let header = b"PF\n3 2\n-1.0\n";
+    assert_eq!(&bytes[..header.len()], header);
+    let floats: Vec<f32> = bytes[header.len()..]
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+        .collect();
+    assert_eq!(floats.len(), 3 * 2 * 3);
+
+    // the last film row comes first
+    let film = film();
+    for (i, pixel) in film.iter().rev().flatten().enumerate() {
+        assert_eq!(
+            &floats[3 * i..3 * i + 3],
+            &[pixel.x as f32, pixel.y as f32, pixel.z as f32]
+        );
+    }
+}
+
+#[test]
+fn exr_round_trips() {
+    let path = temp_path("film.exr");
+    write_exr(&path, &film()).expect("write the EXR file");
+    let image = image::open(&path)
+        .expect("read the EXR file back")
+        .into_rgb32f();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(image.dimensions(), (3, 2));
+    for (row, pixels) in film().iter().enumerate() {
+        for (col, pixel) in pixels.iter().enumerate() {
+            let read = image.get_pixel(col as u32, row as u32).0;
+            assert_eq!(read, [pixel.x as f32, pixel.y as f32, pixel.z as f32]);
+        }
+    }
+}